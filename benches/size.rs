@@ -0,0 +1,40 @@
+// Copyright 2015 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Micro-benchmark of `Interval::size()` in a counting loop, run with `cargo bench`.
+
+extern crate gcollections;
+extern crate interval;
+
+use interval::Interval;
+use interval::ops::*;
+use gcollections::ops::*;
+use std::hint::black_box;
+use std::time::Instant;
+
+const ROUNDS: u32 = 10_000;
+
+fn main() {
+  // One empty interval every ten to also exercise the empty branch.
+  let intervals: Vec<Interval<i32>> = (0..1000)
+    .map(|i| if i % 10 == 0 { Interval::empty() } else { Interval::new(-i, i) })
+    .collect();
+
+  let start = Instant::now();
+  let mut total: u64 = 0;
+  for _ in 0..ROUNDS {
+    for i in &intervals {
+      total += black_box(i).size() as u64;
+    }
+  }
+  let elapsed = start.elapsed();
+
+  let calls = ROUNDS as u64 * intervals.len() as u64;
+  println!("size: {} calls in {:?} ({:.2} ns/call, checksum {})",
+    calls, elapsed, elapsed.as_nanos() as f64 / calls as f64, total);
+}
@@ -8,9 +8,9 @@
 
 //! Closed and bounded generic interval.
 //!
-//! Let `D` be an ordered set and `{i,j} ∈ D`. The interval `I` whose bounds are `{i,j}` is defined as `I = {x ∈ D | i <= x <= j}` and is denoted as `[i..j]`. Only interval with bound types implementing `Num` and `Width` is currently available.
+//! Let `D` be an ordered set and `{i,j} ∈ D`. The interval `I` whose bounds are `{i,j}` is defined as `I = {x ∈ D | i <= x <= j}` and is denoted as `[i..j]`. Only interval with bound types implementing `Num` and `Width` is currently available, except for `Interval<f64>` which can be narrowed to the integers it contains with `to_integer_interval`.
 //!
-//! Most of the operations in `gcollections::ops::*` are implemented. Intervals specific operations, proposed in `ops::*`, are also implemented. There is no `union` operation since this interval representation is not precise enough, and so an union could be over-approximated. For example, consider `[1..2] U [5..6]`, the only possible representation is `[1..6]` which is not exact by the definition of union of sets. However, this operation exists and is named `hull`.
+//! Most of the operations in `gcollections::ops::*` are implemented. Intervals specific operations, proposed in `ops::*`, are also implemented. There is no `union` operation since this interval representation is not precise enough, and so an union could be over-approximated. For example, consider `[1..2] U [5..6]`, the only possible representation is `[1..6]` which is not exact by the definition of union of sets. However, this operation exists and is named `hull`. Also note that the operator `-` is the arithmetic (Minkowski) difference of two intervals (`[5..10] - [1..2] = [3..9]`), while the set difference is the operation `difference` (`[5..10] \ [1..2] = [5..10]`).
 //!
 //! # Examples
 //!
@@ -41,11 +41,29 @@ use gcollections::*;
 use gcollections::ops::*;
 use trilean::SKleene;
 use ops::*;
+use interval_set::IntervalSet;
 
-use std::ops::{Add, Sub, Mul};
-use std::cmp::{min, max};
+use std::ops::{Add, Sub, Mul, Div};
+use std::cmp::{min, max, Ordering};
 use std::fmt::{Formatter, Display, Error};
-use num::{Zero, Num};
+use std::convert::TryFrom;
+use num::{Zero, One, Num, Integer, ToPrimitive, CheckedAdd, CheckedSub, CheckedMul};
+#[cfg(feature = "quickcheck")]
+use quickcheck::{Arbitrary, Gen};
+#[cfg(feature = "proptest")]
+use proptest::strategy::{Strategy, BoxedStrategy, Just, Union as StrategyUnion};
+#[cfg(feature = "proptest")]
+use std::fmt::Debug;
+#[cfg(feature = "proptest")]
+use std::ops::RangeInclusive;
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "rand")]
+use rand::distributions::uniform::SampleUniform;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+#[cfg(feature = "serde")]
+use serde::de::Error as DeError;
 
 /// Closed interval (endpoints included).
 #[derive(Debug, Copy, Clone)]
@@ -108,6 +126,35 @@ impl<Bound> Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Zero + One
+{
+  /// The singleton `[0..0]`, identity of the interval addition.
+  pub fn zero() -> Interval<Bound> {
+    Interval { lb: Bound::zero(), ub: Bound::zero() }
+  }
+
+  /// The singleton `[1..1]`, identity of the interval multiplication.
+  pub fn one() -> Interval<Bound> {
+    Interval { lb: Bound::one(), ub: Bound::one() }
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num
+{
+  /// Same as `Interval::new(lb, ub)`, so `(lb, ub)` with `lb > ub` gives the empty interval.
+  pub fn from_tuple((lb, ub): (Bound, Bound)) -> Interval<Bound> {
+    Interval::new(lb, ub)
+  }
+
+  /// The pair `(lb, ub)`, or `None` if the interval is empty.
+  pub fn as_tuple(&self) -> Option<(Bound, Bound)> {
+    if self.is_empty() { None }
+    else { Some((self.low(), self.up())) }
+  }
+}
+
 impl<Bound> Range for Interval<Bound> where
  Bound: Width
 {
@@ -124,13 +171,23 @@ impl<Bound> Bounded for Interval<Bound> where
  Bound: Num + Width + Clone
 {
   fn lower(&self) -> Bound {
-    debug_assert!(!self.is_empty(), "Cannot access lower bound on empty interval.");
-    self.low()
+    self.try_lower().expect("Cannot access lower bound on empty interval.")
   }
 
   fn upper(&self) -> Bound {
-    debug_assert!(!self.is_empty(), "Cannot access upper bound on empty interval.");
-    self.up()
+    self.try_upper().expect("Cannot access upper bound on empty interval.")
+  }
+}
+
+impl<Bound> TryBounded for Interval<Bound> where
+ Bound: Num + Width + Clone
+{
+  fn try_lower(&self) -> Option<Bound> {
+    if self.is_empty() { None } else { Some(self.low()) }
+  }
+
+  fn try_upper(&self) -> Option<Bound> {
+    if self.is_empty() { None } else { Some(self.up()) }
   }
 }
 
@@ -164,6 +221,7 @@ impl<Bound> Cardinality for Interval<Bound> where
 {
   type Size = <Bound as Width>::Output;
 
+  #[inline]
   fn size(&self) -> <Bound as Width>::Output {
     if self.lb > self.ub { <<Bound as Width>::Output>::zero() }
     else {
@@ -172,6 +230,46 @@ impl<Bound> Cardinality for Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num,
+ <Bound as Width>::Output: ToPrimitive
+{
+  /// Non-panicking companion of `size()`, `None` is returned if the cardinality cannot be represented in a `u128`.
+  pub fn checked_size(&self) -> Option<u128> {
+    self.size().to_u128()
+  }
+
+  /// Fraction of the elements of `self` that are also in `sub`, the probability of `sub` under a uniform distribution over `self`. It is `0.0` if `self` is empty.
+  pub fn probability_within(&self, sub: &Interval<Bound>) -> f64 {
+    if self.is_empty() { 0.0 }
+    else {
+      self.intersection(sub).size().to_f64().unwrap() / self.size().to_f64().unwrap()
+    }
+  }
+
+  /// Fraction of the elements of `reference` covered by `self`, that is `reference.probability_within(self)`. It is `0.0` if `reference` is empty.
+  pub fn coverage_of(&self, reference: &Interval<Bound>) -> f64 {
+    reference.probability_within(self)
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Ord + ToPrimitive
+{
+  /// Exact number of elements computed from the bounds in `u128`, so it does not overflow even for intervals built outside of the `Width` limits, such as `[i64::MIN..i64::MAX]` with `new_discrete`.
+  pub fn size_u128(&self) -> u128 {
+    if self.lb > self.ub { 0 }
+    else {
+      let distance = match (self.lb.to_i128(), self.ub.to_i128()) {
+        // Exact in two's complement since `lb <= ub`.
+        (Some(lb), Some(ub)) => ub.wrapping_sub(lb) as u128,
+        _ => self.ub.to_u128().unwrap() - self.lb.to_u128().unwrap()
+      };
+      distance.saturating_add(1)
+    }
+  }
+}
+
 impl<Bound> Disjoint for Interval<Bound> where
  Bound: Width + Num
 {
@@ -308,6 +406,61 @@ impl<Bound> Hull<Bound> for Interval<Bound> where
   }
 }
 
+impl<Bound> ConvexHull for Interval<Bound> where
+ Bound: Width + Num
+{
+  type Output = Interval<Bound>;
+
+  fn convex_hull(&self) -> Interval<Bound> {
+    self.clone()
+  }
+}
+
+/// The interval from the lower to the upper bound of the contained value, it is empty if there is none.
+impl<T> ConvexHull for Optional<T> where
+ T: Bounded + Cardinality,
+ <T as Collection>::Item: Width + Num
+{
+  type Output = Interval<<T as Collection>::Item>;
+
+  fn convex_hull(&self) -> Self::Output {
+    match self.as_ref() {
+      Some(x) if !x.is_empty() => Interval::new(x.lower(), x.upper()),
+      _ => Interval::empty()
+    }
+  }
+}
+
+/// The number of values lying strictly between both intervals: the bounds are integers, so `[1..3]` and `[4..6]` are adjacent and have a distance of `0`, like overlapping and identical intervals, while `[1..3]` and `[6..8]` have a distance of `2`. `None` is returned if one of the intervals is empty.
+impl<Bound> Distance for Interval<Bound> where
+ Bound: Width + Num
+{
+  type Output = Option<<Bound as Width>::Output>;
+
+  fn distance(&self, other: &Interval<Bound>) -> Self::Output {
+    if self.is_empty() || other.is_empty() { return None; }
+    let (left, right) = if self.up() < other.low() { (self, other) } else { (other, self) };
+    if left.up() >= right.low() || left.up() + Bound::one() == right.low() {
+      Some(<<Bound as Width>::Output>::zero())
+    }
+    else {
+      Some(Bound::width(&(left.up() + Bound::one()), &(right.low() - Bound::one())))
+    }
+  }
+}
+
+/// The distance between the convex hulls of the contained values, `None` is returned if one of them is absent or empty.
+impl<T> Distance for Optional<T> where
+ T: Bounded + Cardinality,
+ <T as Collection>::Item: Width + Num
+{
+  type Output = Option<<<T as Collection>::Item as Width>::Output>;
+
+  fn distance(&self, other: &Optional<T>) -> Self::Output {
+    self.convex_hull().distance(&other.convex_hull())
+  }
+}
+
 macro_rules! primitive_interval_hull
 {
   ( $( $source:ty ),* ) =>
@@ -333,6 +486,15 @@ impl<Bound> Contains for Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Ord
+{
+  /// Strict version of `contains`: `value` must be in the interval but different from its bounds.
+  pub fn contains_exclusive(&self, value: &Bound) -> bool {
+    value > &self.lb && value < &self.ub
+  }
+}
+
 impl<Bound> Subset for Interval<Bound> where
  Bound: Width + Num
 {
@@ -352,6 +514,20 @@ impl<Bound> ProperSubset for Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num
+{
+  /// `other ⊆ self`, the same as `other.is_subset(self)` phrased from the container.
+  pub fn contains_interval(&self, other: &Interval<Bound>) -> bool {
+    other.is_subset(self)
+  }
+
+  /// `other ⊂ self`, the same as `other.is_proper_subset(self)` phrased from the container.
+  pub fn strictly_contains(&self, other: &Interval<Bound>) -> bool {
+    other.is_proper_subset(self)
+  }
+}
+
 impl<Bound> Intersection for Interval<Bound> where
  Bound: Width + Num
 {
@@ -365,6 +541,102 @@ impl<Bound> Intersection for Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num
+{
+  /// Clamps the bounds into `[Width::min_value()..Width::max_value()]`, for example after they were produced by the saturating arithmetic of the primitive types, which can reach `i32::MIN` or `u8::MAX`. It is a no-op for a valid interval.
+  pub fn clamp_to_type(&self) -> Interval<Bound> {
+    if self.lb > self.ub { Interval::empty() }
+    else {
+      Interval::new(
+        max(self.low(), <Bound as Width>::min_value()),
+        min(self.up(), <Bound as Width>::max_value()))
+    }
+  }
+
+  /// Copy of `self` with the lower bound replaced by `lb`, it is empty if `lb > ub` or if `self` is empty.
+  pub fn with_lower(&self, lb: Bound) -> Interval<Bound> {
+    if self.is_empty() { Interval::empty() }
+    else { Interval::new(lb, self.up()) }
+  }
+
+  /// Copy of `self` with the upper bound replaced by `ub`, it is empty if `lb > ub` or if `self` is empty.
+  pub fn with_upper(&self, ub: Bound) -> Interval<Bound> {
+    if self.is_empty() { Interval::empty() }
+    else { Interval::new(self.low(), ub) }
+  }
+
+  /// Truncates `self` to its elements lower than `at` and returns the elements greater or equal to `at`, similarly to `Vec::split_off`. One of the two parts is empty if `at` is outside of `self`.
+  pub fn split_off(&mut self, at: Bound) -> Interval<Bound> {
+    if self.is_empty() || at > self.ub {
+      Interval::empty()
+    }
+    else if at <= self.lb {
+      ::std::mem::replace(self, Interval::empty())
+    }
+    else {
+      let right = Interval::new(at.clone(), self.up());
+      self.ub = at - Bound::one();
+      right
+    }
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num
+{
+  /// Smallest interval enclosing `self` whose bounds lie on the grid `origin + k*step`: `lb` is rounded down and `ub` up to a grid line. The empty interval is left unchanged.
+  ///
+  /// Precondition: `step > 0` and the rounded bounds do not overflow (for unsigned bounds, `origin <= lb`).
+  pub fn align_to_grid(&self, origin: Bound, step: Bound) -> Interval<Bound> {
+    debug_assert!(step > Bound::zero(), "The step of a grid must be positive.");
+    if self.is_empty() { return self.clone(); }
+    // Distance from `b` down to the previous grid line, in `[0..step-1]`.
+    let phase = |b: Bound| {
+      let r = (b - origin.clone()) % step.clone();
+      if r < Bound::zero() { r + step.clone() } else { r }
+    };
+    let lb = self.low() - phase(self.low());
+    let ub_phase = phase(self.up());
+    let ub =
+      if ub_phase == Bound::zero() { self.up() }
+      else { self.up() + (step.clone() - ub_phase) };
+    Interval::new(lb, ub)
+  }
+
+  /// Scales the interval by `factor` around `center`, each bound `b` goes to `center + (b - center) * factor`. The bounds are swapped if `factor` is negative, and the empty interval is left unchanged.
+  pub fn scale_about(&self, center: Bound, factor: Bound) -> Interval<Bound> {
+    if self.is_empty() { self.clone() }
+    else {
+      let scale = |b: Bound| center.clone() + (b - center.clone()) * factor.clone();
+      let lb = scale(self.low());
+      let ub = scale(self.up());
+      Interval::new(min(lb.clone(), ub.clone()), max(lb, ub))
+    }
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num
+{
+  /// Intersection of all the intervals of the slice, it stops as soon as the intersection is empty. The intersection of an empty slice is `Interval::whole()`, the identity of the intersection.
+  pub fn intersect_all(intervals: &[Interval<Bound>]) -> Interval<Bound> {
+    let mut res = Interval::whole();
+    for i in intervals {
+      res = res.intersection(i);
+      if res.is_empty() { break; }
+    }
+    res
+  }
+
+  /// The intersection of `self` and `other`, or `None` if it is empty.
+  pub fn intersect_nonempty(&self, other: &Interval<Bound>) -> Option<Interval<Bound>> {
+    let res = self.intersection(other);
+    if res.is_empty() { None }
+    else { Some(res) }
+  }
+}
+
 impl<Bound> Intersection<Bound> for Interval<Bound> where
  Bound: Width + Num
 {
@@ -516,6 +788,39 @@ impl<'a, 'b, Bound> Add<&'b Interval<Bound>> for &'a Interval<Bound> where
 
 forward_all_binop!(impl<Bound: +Num+Width+Clone> Add for Interval<Bound>, add, Bound);
 
+// `a + b` saturated to the limits of `Width`, with `true` if it saturated.
+fn saturating_add_bound<Bound>(a: Bound, b: Bound) -> (Bound, bool) where
+ Bound: Width + Num + CheckedAdd
+{
+  let min = <Bound as Width>::min_value();
+  let max = <Bound as Width>::max_value();
+  match a.checked_add(&b) {
+    Some(sum) =>
+      if sum < min { (min, true) }
+      else if sum > max { (max, true) }
+      else { (sum, false) },
+    None =>
+      if b > Bound::zero() { (max, true) }
+      else { (min, true) }
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num + CheckedAdd
+{
+  /// Same as `self + other` but the bounds saturate to the limits of `Width` instead of overflowing, the flag is `true` if one of the bounds saturated. Similar to `overflowing_add` of the primitive types.
+  pub fn overflowing_add(&self, other: &Interval<Bound>) -> (Interval<Bound>, bool) {
+    if self.is_empty() || other.is_empty() {
+      (Interval::empty(), false)
+    }
+    else {
+      let (lb, lb_overflow) = saturating_add_bound(self.low(), other.low());
+      let (ub, ub_overflow) = saturating_add_bound(self.up(), other.up());
+      (Interval::new(lb, ub), lb_overflow || ub_overflow)
+    }
+  }
+}
+
 impl<'a, 'b, Bound> Add<&'b Bound> for &'a Interval<Bound> where
  Bound: Num + Width + Clone
 {
@@ -538,6 +843,7 @@ impl<'a, 'b, Bound> Sub<&'b Interval<Bound>> for &'a Interval<Bound> where
 {
   type Output = Interval<Bound>;
 
+  // Minkowski difference `{x - y | x in self, y in other}`, not to be confused with the set difference (see `Difference`).
   fn sub(self, other: &Interval<Bound>) -> Interval<Bound> {
     if self.is_empty() || other.is_empty() {
       Interval::empty()
@@ -648,6 +954,125 @@ impl<'a, 'b, Bound> Mul<&'b Bound> for &'a Interval<Bound> where
   }
 }
 
+// `Some(b)` if `b` is within the limits of `Width`.
+fn within_width<Bound: Width>(b: Option<Bound>) -> Option<Bound> {
+  b.filter(|b| *b >= Bound::min_value() && *b <= Bound::max_value())
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num + CheckedAdd + CheckedSub + CheckedMul
+{
+  /// Same as `self + other` but `None` is returned if a bound overflows the limits of `Width`.
+  pub fn checked_add(&self, other: &Interval<Bound>) -> Option<Interval<Bound>> {
+    if self.is_empty() || other.is_empty() { return Some(Interval::empty()); }
+    Some(Interval::new(
+      within_width(self.low().checked_add(&other.low()))?,
+      within_width(self.up().checked_add(&other.up()))?))
+  }
+
+  /// Same as `self - other` but `None` is returned if a bound overflows the limits of `Width`.
+  pub fn checked_sub(&self, other: &Interval<Bound>) -> Option<Interval<Bound>> {
+    if self.is_empty() || other.is_empty() { return Some(Interval::empty()); }
+    Some(Interval::new(
+      within_width(self.low().checked_sub(&other.up()))?,
+      within_width(self.up().checked_sub(&other.low()))?))
+  }
+
+  /// Same as `self * other` but `None` is returned if one of the products overflows the limits of `Width`.
+  pub fn checked_mul(&self, other: &Interval<Bound>) -> Option<Interval<Bound>> {
+    if self.is_empty() || other.is_empty() { return Some(Interval::empty()); }
+    let mut products = vec![];
+    for a in &[self.low(), self.up()] {
+      for b in &[other.low(), other.up()] {
+        products.push(within_width(a.checked_mul(b))?);
+      }
+    }
+    let (min, max) = min_max(products.into_iter());
+    Some(Interval::new(min, max))
+  }
+
+  /// Same as `self * other` but the overflowing products saturate to the limit of `Width` in the direction of their sign, so the result still encloses the products representable in `Bound`.
+  pub fn saturating_mul(&self, other: &Interval<Bound>) -> Interval<Bound> {
+    if self.is_empty() || other.is_empty() { return Interval::empty(); }
+    let zero = Bound::zero();
+    let mut products = vec![];
+    for a in &[self.low(), self.up()] {
+      for b in &[other.low(), other.up()] {
+        products.push(within_width(a.checked_mul(b)).unwrap_or_else(||
+          if (*a > zero) == (*b > zero) { Bound::max_value() }
+          else { Bound::min_value() }));
+      }
+    }
+    let (min, max) = min_max(products.into_iter());
+    Interval::new(min, max)
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num + CheckedAdd + CheckedSub
+{
+  /// Sub-intervals of `self` centered on `center` and growing by `step` on each side, from `[center..center]` to `self` itself: the windows are clamped to the bounds of `self` and the last one is `self`. Nothing is generated if `center` is not in `self`. Panics if `step` is not positive.
+  pub fn expanding_windows(&self, center: Bound, step: Bound) -> impl Iterator<Item=Interval<Bound>> {
+    assert!(step > Bound::zero(), "The step of the windows must be positive.");
+    let whole = self.clone();
+    let first = if self.contains(&center) { Some(Interval::singleton(center)) } else { None };
+    ::std::iter::successors(first, move |window: &Interval<Bound>| {
+      if *window == whole { None }
+      else {
+        // The bounds that overflow are clamped as well.
+        let lb = window.lb.checked_sub(&step).map_or(whole.low(), |lb| max(lb, whole.low()));
+        let ub = window.ub.checked_add(&step).map_or(whole.up(), |ub| min(ub, whole.up()));
+        Some(Interval::new(lb, ub))
+      }
+    })
+  }
+}
+
+forward_all_binop!(impl<Bound: +Num+Width+Integer> Div for Interval<Bound>, div);
+
+impl<Bound> Div<&Interval<Bound>> for &Interval<Bound> where
+ Bound: Num + Width + Integer
+{
+  type Output = Interval<Bound>;
+
+  // Smallest interval enclosing the real quotients `{x / y | x in self, y in other, y != 0}`, the bounds are rounded outward so `[1,1] / [2,2]` is `[0,1]`.
+  // Zero is removed from `other`: if it straddles zero, the result is the hull of the quotients by its negative and positive parts (the gap between both is over-approximated), and if `other` is `[0,0]` the result is empty.
+  fn div(self, other: &Interval<Bound>) -> Interval<Bound> {
+    let zero = Bound::zero();
+    if self.is_empty() || other.is_empty() {
+      return Interval::empty();
+    }
+    let negative =
+      if other.lower() < zero { Interval::new(other.lower(), min(other.upper(), zero.clone() - Bound::one())) }
+      else { Interval::empty() };
+    let positive =
+      if other.upper() > zero { Interval::new(max(other.lower(), Bound::one()), other.upper()) }
+      else { Interval::empty() };
+    div_zero_free(self, &negative).hull(&div_zero_free(self, &positive))
+  }
+}
+
+// `x / y` rounded outward, with `y` not containing zero. The extrema of the quotient are reached on the bounds.
+fn div_zero_free<Bound>(x: &Interval<Bound>, y: &Interval<Bound>) -> Interval<Bound> where
+ Bound: Num + Width + Integer
+{
+  if y.is_empty() {
+    return Interval::empty();
+  }
+  let div_ceil = |a: Bound, b: Bound| {
+    let q = a.div_floor(&b);
+    if a.mod_floor(&b).is_zero() { q } else { q + Bound::one() }
+  };
+  let pairs = [
+    (x.lower(), y.lower()),
+    (x.lower(), y.upper()),
+    (x.upper(), y.lower()),
+    (x.upper(), y.upper())];
+  let lb = pairs.iter().map(|(a, b)| a.div_floor(b)).min().unwrap();
+  let ub = pairs.iter().map(|(a, b)| div_ceil(a.clone(), b.clone())).max().unwrap();
+  Interval::new(lb, ub)
+}
+
 impl<Bound> Display for Interval<Bound> where
  Bound: Display + Width + Num
 {
@@ -660,6 +1085,58 @@ impl<Bound> Display for Interval<Bound> where
   }
 }
 
+impl<Bound> Interval<Bound> where
+ Bound: Display + Width + Num
+{
+  /// Number of characters of the widest bound once formatted (including the minus sign), useful to align a column of intervals. It is `0` for the empty interval.
+  pub fn max_digit_width(&self) -> usize {
+    if self.is_empty() { 0 }
+    else { max(self.lb.to_string().len(), self.ub.to_string().len()) }
+  }
+}
+
+/// Helper struct for displaying an interval whose bounds at the limits of `Width` are shown as infinite.
+///
+/// Created by `Interval::display_unbounded`.
+pub struct DisplayUnbounded<'a, Bound: 'a>
+{
+  interval: &'a Interval<Bound>
+}
+
+impl<Bound> Interval<Bound>
+{
+  /// Display `[-∞..10]` instead of `[-2147483647..10]` for an `Interval<i32>` ranging from `Width::min_value()` to `10`. The lower bound of unsigned types is always displayed since `0` is not unbounded, so `[0..+∞]` is displayed for the whole `Interval<u32>`.
+  pub fn display_unbounded(&self) -> DisplayUnbounded<'_, Bound> {
+    DisplayUnbounded { interval: self }
+  }
+}
+
+impl<'a, Bound> Display for DisplayUnbounded<'a, Bound> where
+ Bound: Display + Width + Num
+{
+  fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+    let interval = self.interval;
+    if interval.is_empty() {
+      formatter.write_str("{}")
+    } else {
+      formatter.write_str("[")?;
+      let min = <Bound as Width>::min_value();
+      if interval.lb == min && min < Bound::zero() {
+        formatter.write_str("-∞")?;
+      } else {
+        interval.lb.fmt(formatter)?;
+      }
+      formatter.write_str("..")?;
+      if interval.ub == <Bound as Width>::max_value() {
+        formatter.write_str("+∞")?;
+      } else {
+        interval.ub.fmt(formatter)?;
+      }
+      formatter.write_str("]")
+    }
+  }
+}
+
 pub trait ToInterval<Bound>
 {
   fn to_interval(self) -> Interval<Bound>;
@@ -715,28 +1192,502 @@ impl<Bound> Entailment for Interval<Bound> where
     if self.is_subset(other) {
       SKleene::True
     }
-    else if other.is_subset(self) {
-      SKleene::False
+    else if other.is_subset(self) {
+      SKleene::False
+    }
+    else {
+      SKleene::Unknown
+    }
+  }
+}
+
+impl<Bound> Top for Interval<Bound> where
+ Bound: Width + Num
+{
+  fn top() -> Interval<Bound> {
+    Interval::empty()
+  }
+}
+
+impl<Bound> Bot for Interval<Bound> where
+ Bound: Width + Num
+{
+  fn bot() -> Interval<Bound> {
+    Interval::whole()
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Step
+{
+  /// Same as `Interval::new(lb, ub)` but only requires `Step`, for discrete domains that are not numbers. The interval is empty if `lb > ub`.
+  pub fn new_discrete(lb: Bound, ub: Bound) -> Interval<Bound> {
+    Interval { lb, ub }
+  }
+
+  /// Iterates over the elements of the interval in increasing order.
+  pub fn iter(&self) -> StepIter<Bound> {
+    StepIter {
+      next: if self.lb > self.ub { None } else { Some(self.low()) },
+      ub: self.up()
+    }
+  }
+
+  /// Iterates over at most `max` elements of the interval, starting from the lower bound.
+  pub fn iter_capped(&self, max: usize) -> ::std::iter::Take<StepIter<Bound>> {
+    self.iter().take(max)
+  }
+
+  /// Number of elements of the interval satisfying `pred`, each element is tested.
+  pub fn count_matching<F>(&self, pred: F) -> usize where
+   F: Fn(Bound) -> bool
+  {
+    self.iter().filter(|x| pred(x.clone())).count()
+  }
+
+  /// Number of elements in the interval, the counterpart of `size()` for `Step` bounds. `None` is returned if it does not fit in a `usize`.
+  pub fn step_count(&self) -> Option<usize> {
+    if self.lb > self.ub { Some(0) }
+    else { Bound::steps_between(&self.lb, &self.ub).and_then(|n| n.checked_add(1)) }
+  }
+
+  /// `true` if both intervals are non-empty and one starts right after the end of the other.
+  pub fn is_adjacent_to(&self, other: &Interval<Bound>) -> bool {
+    fn follows<B: Step>(a: &Interval<B>, b: &Interval<B>) -> bool {
+      a.ub.successor().as_ref() == Some(&b.lb)
+    }
+    self.lb <= self.ub && other.lb <= other.ub
+      && (follows(self, other) || follows(other, self))
+  }
+}
+
+/// Iterator over the elements of an interval with `Step` bounds, see `Interval::iter`.
+#[derive(Debug, Clone)]
+pub struct StepIter<Bound>
+{
+  next: Option<Bound>,
+  ub: Bound
+}
+
+impl<Bound> Iterator for StepIter<Bound> where
+ Bound: Step
+{
+  type Item = Bound;
+
+  fn next(&mut self) -> Option<Bound> {
+    let current = self.next.take()?;
+    if current < self.ub {
+      self.next = current.successor();
+    }
+    Some(current)
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match self.next {
+      None => (0, Some(0)),
+      Some(ref next) => match Bound::steps_between(next, &self.ub).and_then(|n| n.checked_add(1)) {
+        Some(n) => (n, Some(n)),
+        None => (usize::MAX, None)
+      }
+    }
+  }
+}
+
+macro_rules! step_iter_exact_size
+{
+  ( $( $t: ty ),* ) =>
+  {$(
+    impl ExactSizeIterator for StepIter<$t> {}
+  )*}
+}
+
+// Same as `RangeInclusive` in the standard library: the size of the other types might not fit in a `usize` (`[0..usize::MAX]` has `usize::MAX + 1` elements).
+step_iter_exact_size!(u8,u16,i8,i16);
+
+impl<Bound> IntoIterator for Interval<Bound> where
+ Bound: Step
+{
+  type Item = Bound;
+  type IntoIter = StepIter<Bound>;
+
+  fn into_iter(self) -> StepIter<Bound> {
+    self.iter()
+  }
+}
+
+impl<Bound> IntoIterator for &Interval<Bound> where
+ Bound: Step
+{
+  type Item = Bound;
+  type IntoIter = StepIter<Bound>;
+
+  fn into_iter(self) -> StepIter<Bound> {
+    self.iter()
+  }
+}
+
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num + ToPrimitive
+{
+  /// Interpolates each bound linearly from `self` (at `t = 0`) to `other` (at `t = 1`). Values of `t` outside of `[0..1]` are not clamped and extrapolate the bounds. The result is empty if one of the intervals is empty.
+  pub fn lerp(&self, other: &Interval<Bound>, t: f64) -> Interval<f64> {
+    if self.is_empty() || other.is_empty() {
+      Interval::from_f64(1.0, 0.0)
+    }
+    else {
+      let lerp_bound = |a: &Bound, b: &Bound| {
+        let a = a.to_f64().unwrap();
+        a + (b.to_f64().unwrap() - a) * t
+      };
+      Interval::from_f64(lerp_bound(&self.lb, &other.lb), lerp_bound(&self.ub, &other.ub))
+    }
+  }
+}
+
+impl Interval<i64>
+{
+  /// Presence mask of `self` over the window `[base..base+len-1]`: the index `i` is `true` iff `base + i` is in `self`, the values outside of the window are ignored. `None` is returned if the window exceeds the bounds allowed for `i64` (see `Width`).
+  pub fn to_mask(&self, base: i64, len: usize) -> Option<Vec<bool>> {
+    if base < <i64 as Width>::min_value() { return None; }
+    let window =
+      if len == 0 { Interval::empty() }
+      else { Interval::new(base, base.checked_add(i64::try_from(len - 1).ok()?)?) };
+    let mut mask = vec![false; len];
+    let inside = self.intersection(&window);
+    if !inside.is_empty() {
+      let first = (inside.lower() - base) as usize;
+      let last = (inside.upper() - base) as usize;
+      for i in &mut mask[first..=last] {
+        *i = true;
+      }
+    }
+    Some(mask)
+  }
+
+  /// `true` if the `count` terms `start + k * step` (with `k` in `[0..count-1]`) are all in `self`, since `self` is convex only the first and last terms are checked. It is always `true` if `count` is zero.
+  pub fn contains_progression(&self, start: i64, step: i64, count: usize) -> bool {
+    if count == 0 { return true; }
+    // Cannot overflow: `|(count - 1) * step + start| < 2^127`.
+    let last = start as i128 + (count as i128 - 1) * step as i128;
+    self.contains(&start)
+      && i64::try_from(last).is_ok_and(|last| self.contains(&last))
+  }
+
+  /// The two probe points of golden-section search, at the golden ratio from each bound and rounded to the nearest integers. They lie strictly inside `self` if it has at least three elements (both points are equal if it has exactly three). Panics if `self` is empty.
+  pub fn golden_split(&self) -> (i64, i64) {
+    assert!(!self.is_empty(), "Cannot split an empty interval.");
+    // `2 - φ`, the offset of the probes relative to the width.
+    let ratio = (3.0 - 5f64.sqrt()) / 2.0;
+    let width = self.ub as i128 - self.lb as i128;
+    // At least `1` as soon as `width >= 2`, and at most `width / 2`.
+    let offset = (width as f64 * ratio).round() as i128;
+    ((self.lb as i128 + offset) as i64, (self.ub as i128 - offset) as i64)
+  }
+
+  /// Leaves of the repeated bisection of `self` until every piece has at most `target` elements, in increasing order. The leaves partition `self` and the lower half gets the extra element of an odd piece. Panics if `target` is not positive.
+  pub fn bisect_to_size(&self, target: i64) -> Vec<Interval<i64>> {
+    assert!(target > 0, "The target size of the pieces must be positive.");
+    let mut leaves = vec![];
+    let mut pending = if self.is_empty() { vec![] } else { vec![*self] };
+    while let Some(piece) = pending.pop() {
+      // Computed in `i128` since the width of `[i64::MIN+1..i64::MAX]` does not fit in `i64`.
+      let width = piece.ub as i128 - piece.lb as i128;
+      if width < target as i128 {
+        leaves.push(piece);
+      } else {
+        let mid = (piece.lb as i128 + width / 2) as i64;
+        pending.push(Interval::new(mid + 1, piece.ub));
+        pending.push(Interval::new(piece.lb, mid));
+      }
+    }
+    leaves
+  }
+
+  /// Elements of `self` on the progression `start + k * step` for every integer `k`, as singletons which are coalesced into `self` when `step` is `1` or `-1`. If `step` is zero the progression is `{start}`.
+  pub fn intersect_progression(&self, start: i64, step: i64) -> IntervalSet<i64> {
+    if self.is_empty() { return IntervalSet::empty(); }
+    let step = (step as i128).abs();
+    if step == 0 {
+      return if self.contains(&start) { IntervalSet::singleton(start) } else { IntervalSet::empty() };
+    }
+    if step == 1 { return IntervalSet::new(self.lb, self.ub); }
+    // Smallest term greater or equal to `lb`, computed in `i128` to not overflow.
+    let first = self.lb as i128 + (start as i128 - self.lb as i128).rem_euclid(step);
+    (0..).map(|k| first + k * step)
+      .take_while(|&x| x <= self.ub as i128)
+      .map(|x| Interval::singleton(x as i64))
+      .collect()
+  }
+}
+
+/// Rounding of the bounds computed by the floating point operations of `Interval<f64>`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+  /// The bounds are moved one ulp away from each other, so the result encloses the exact one.
+  #[default]
+  Outward,
+  /// The bounds are rounded to the nearest floating point numbers, which is faster but not rigorous.
+  Nearest,
+  /// The bounds are moved one ulp toward each other, so the result is included in the exact one (it might become empty).
+  Inward
+}
+
+impl RoundingMode {
+  fn round(self, lb: f64, ub: f64) -> Interval<f64> {
+    match self {
+      RoundingMode::Outward => Interval::from_f64(lb.next_down(), ub.next_up()),
+      RoundingMode::Nearest => Interval::from_f64(lb, ub),
+      RoundingMode::Inward => Interval::from_f64(lb.next_up(), ub.next_down())
+    }
+  }
+}
+
+/// Floating point intervals only support the arithmetic with an explicit `RoundingMode`, rounding and conversions to other bound types, the other operations require a `Width` bound.
+impl Interval<f64>
+{
+  /// Closed interval of reals `[lb..ub]`, it is empty if `lb > ub` or if a bound is NaN.
+  pub fn from_f64(lb: f64, ub: f64) -> Interval<f64> {
+    if lb <= ub { Interval { lb, ub } }
+    else { Interval { lb: 1.0, ub: 0.0 } }
+  }
+
+  fn is_empty_f64(&self) -> bool {
+    self.lb > self.ub
+  }
+
+  /// `self + other` with the bounds rounded according to `mode`, the result is empty if one of the operands is empty.
+  pub fn add_rounded(&self, other: &Interval<f64>, mode: RoundingMode) -> Interval<f64> {
+    if self.is_empty_f64() || other.is_empty_f64() { Interval::from_f64(1.0, 0.0) }
+    else { mode.round(self.lb + other.lb, self.ub + other.ub) }
+  }
+
+  /// `self - other` with the bounds rounded according to `mode`, the result is empty if one of the operands is empty.
+  pub fn sub_rounded(&self, other: &Interval<f64>, mode: RoundingMode) -> Interval<f64> {
+    if self.is_empty_f64() || other.is_empty_f64() { Interval::from_f64(1.0, 0.0) }
+    else { mode.round(self.lb - other.ub, self.ub - other.lb) }
+  }
+
+  /// `self * other` with the bounds rounded according to `mode`, the result is empty if one of the operands is empty. The undefined products `0 * ∞` are ignored.
+  pub fn mul_rounded(&self, other: &Interval<f64>, mode: RoundingMode) -> Interval<f64> {
+    if self.is_empty_f64() || other.is_empty_f64() { Interval::from_f64(1.0, 0.0) }
+    else {
+      let products = [
+        self.lb * other.lb,
+        self.lb * other.ub,
+        self.ub * other.lb,
+        self.ub * other.ub];
+      mode.round(
+        products.iter().cloned().fold(f64::INFINITY, f64::min),
+        products.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
+    }
+  }
+
+  /// Rounds both bounds down, the empty interval is left unchanged.
+  pub fn floor(&self) -> Interval<f64> {
+    if self.is_empty_f64() { *self }
+    else { Interval::from_f64(self.lb.floor(), self.ub.floor()) }
+  }
+
+  /// Rounds both bounds up, the empty interval is left unchanged.
+  pub fn ceil(&self) -> Interval<f64> {
+    if self.is_empty_f64() { *self }
+    else { Interval::from_f64(self.lb.ceil(), self.ub.ceil()) }
+  }
+
+  /// The integers contained in `self`: `lb` is rounded up and `ub` down, and both are clamped to the bounds of `i64`. The result is empty if no integer lies within `self`.
+  pub fn to_integer_interval(&self) -> Interval<i64> {
+    let min_i64 = <i64 as Width>::min_value();
+    let max_i64 = <i64 as Width>::max_value();
+    // The limits are rounded to `-2^63` and `2^63` in `f64`, both outside of the limits of `Width`.
+    if self.is_empty_f64() || self.ub <= min_i64 as f64 || self.lb >= max_i64 as f64 {
+      Interval::empty()
+    }
+    else {
+      let lb = max(self.lb.ceil() as i64, min_i64);
+      let ub = min(self.ub.floor() as i64, max_i64);
+      Interval::new(lb, ub)
+    }
+  }
+
+  /// Converts the bounds to `f32`, `lb` is rounded toward negative infinity and `ub` toward positive infinity so the result always encloses `self`.
+  pub fn to_f32_interval(&self) -> Interval<f32> {
+    if self.is_empty_f64() { Interval { lb: 1.0, ub: 0.0 } }
+    else {
+      let mut lb = self.lb as f32;
+      if lb as f64 > self.lb { lb = lb.next_down(); }
+      let mut ub = self.ub as f32;
+      if (ub as f64) < self.ub { ub = ub.next_up(); }
+      Interval { lb, ub }
+    }
+  }
+}
+
+/// Variants of `contains`, `overlap` and `intersection` only requiring `PartialOrd`, for bounds such as `f64` without a total order.
+/// Incomparable results (e.g. with NaN) are treated conservatively: an interval with incomparable bounds is empty, an incomparable value is not contained and incomparable intervals do not overlap.
+impl<Bound> Interval<Bound> where
+ Bound: PartialOrd + Clone
+{
+  /// `true` if `lb <= ub` does not hold, in particular if one of the bounds is NaN.
+  pub fn is_empty_partial(&self) -> bool {
+    self.lb.partial_cmp(&self.ub).is_none_or(|o| o == Ordering::Greater)
+  }
+
+  /// `true` if `lb <= value <= ub`, it is always `false` for NaN.
+  pub fn contains_partial(&self, value: &Bound) -> bool {
+    self.lb <= *value && *value <= self.ub
+  }
+
+  /// `true` if both intervals are non-empty and share at least one value.
+  pub fn overlap_partial(&self, other: &Interval<Bound>) -> bool {
+    !self.is_empty_partial() && !other.is_empty_partial()
+      && self.lb <= other.ub && other.lb <= self.ub
+  }
+
+  /// The intersection of both intervals, the empty operand is returned if there is one. It is `None` if the bounds of the operands are incomparable, so the intersection cannot be decided.
+  pub fn intersection_partial(&self, other: &Interval<Bound>) -> Option<Interval<Bound>> {
+    if self.is_empty_partial() { return Some(self.clone()); }
+    if other.is_empty_partial() { return Some(other.clone()); }
+    let lb = if self.lb >= other.lb { self.lb.clone() }
+      else if self.lb < other.lb { other.lb.clone() }
+      else { return None; };
+    let ub = if self.ub <= other.ub { self.ub.clone() }
+      else if self.ub > other.ub { other.ub.clone() }
+      else { return None; };
+    Some(Interval { lb, ub })
+  }
+}
+
+/// Requires the `rand` feature.
+#[cfg(feature = "rand")]
+impl<Bound> Interval<Bound> where
+ Bound: Width + Num + SampleUniform
+{
+  /// A random non-empty sub-interval of `self`, its bounds are two values drawn uniformly in `self`. The empty interval is returned if `self` is empty.
+  pub fn random_subinterval<R: Rng>(&self, rng: &mut R) -> Interval<Bound> {
+    if self.is_empty() { Interval::empty() }
+    else {
+      let a = rng.gen_range(self.low()..=self.up());
+      let b = rng.gen_range(self.low()..=self.up());
+      Interval::new(min(a.clone(), b.clone()), max(a, b))
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct BoundsRef<'a, Bound: 'a> {
+  lb: &'a Bound,
+  ub: &'a Bound
+}
+
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct Bounds<Bound> {
+  lb: Bound,
+  ub: Bound
+}
+
+/// Serialized as `{"lb": lb, "ub": ub}`, or as `null` (the serde representation of `None`) for the empty interval.
+#[cfg(feature = "serde")]
+impl<Bound> Serialize for Interval<Bound> where
+ Bound: Width + Num + Serialize
+{
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    let bounds =
+      if self.is_empty() { None }
+      else { Some(BoundsRef { lb: &self.lb, ub: &self.ub }) };
+    bounds.serialize(serializer)
+  }
+}
+
+/// Fails if a bound exceeds the limits of `Width`, and `{"lb": lb, "ub": ub}` with `lb > ub` gives the empty interval, as `Interval::new`.
+#[cfg(feature = "serde")]
+impl<'de, Bound> Deserialize<'de> for Interval<Bound> where
+ Bound: Width + Num + Deserialize<'de>
+{
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Interval<Bound>, D::Error> {
+    match Option::<Bounds<Bound>>::deserialize(deserializer)? {
+      None => Ok(Interval::empty()),
+      Some(Bounds { lb, ub }) => {
+        if lb < <Bound as Width>::min_value() || ub > <Bound as Width>::max_value() {
+          Err(D::Error::custom("interval bound out of the limits of `Width`"))
+        }
+        else if lb > ub { Ok(Interval::empty()) }
+        else { Ok(Interval::new(lb, ub)) }
+      }
+    }
+  }
+}
+
+#[cfg(any(feature = "quickcheck", feature = "proptest"))]
+fn clamp_to_width<Bound: Width>(value: Bound) -> Bound {
+  max(min(value, <Bound as Width>::max_value()), <Bound as Width>::min_value())
+}
+
+/// Generates empty intervals or intervals with bounds between `Width::min_value()` and `Width::max_value()`.
+#[cfg(feature = "quickcheck")]
+impl<Bound> Arbitrary for Interval<Bound> where
+ Bound: Arbitrary + Width + Num
+{
+  fn arbitrary(g: &mut Gen) -> Interval<Bound> {
+    // One interval out of ten is empty.
+    if u8::arbitrary(g) % 10 == 0 {
+      Interval::empty()
+    }
+    else {
+      let a = clamp_to_width(Bound::arbitrary(g));
+      let b = clamp_to_width(Bound::arbitrary(g));
+      Interval::new(min(a.clone(), b.clone()), max(a, b))
+    }
+  }
+
+  // Shrinks toward the empty interval first, and then toward bounds closer to zero.
+  fn shrink(&self) -> Box<dyn Iterator<Item=Interval<Bound>>> {
+    if self.is_empty() {
+      ::quickcheck::empty_shrinker()
     }
     else {
-      SKleene::Unknown
+      let bounds = (self.low(), self.up()).shrink()
+        .filter(|(lb, ub)| lb <= ub)
+        .map(|(lb, ub)| Interval::new(lb, ub));
+      Box::new(Some(Interval::empty()).into_iter().chain(bounds))
     }
   }
 }
 
-impl<Bound> Top for Interval<Bound> where
- Bound: Width + Num
+/// Strategy generating the empty interval with probability `empty_probability`, and otherwise intervals with bounds in `bounds` (clamped to the limits of `Width`). It shrinks toward the empty interval, if it can be generated, and then toward bounds closer to zero. Requires the `proptest` feature.
+#[cfg(feature = "proptest")]
+pub fn interval_strategy<Bound>(bounds: RangeInclusive<Bound>, empty_probability: f64) -> BoxedStrategy<Interval<Bound>> where
+ Bound: Width + Num + Debug + 'static,
+ RangeInclusive<Bound>: Strategy<Value=Bound>
 {
-  fn top() -> Interval<Bound> {
-    Interval::empty()
-  }
+  let (lb, ub) = bounds.into_inner();
+  let bounds = clamp_to_width(lb)..=clamp_to_width(ub);
+  let non_empty = (bounds.clone(), bounds)
+    .prop_map(|(a, b)| Interval::new(min(a.clone(), b.clone()), max(a, b)));
+  // The alternatives are shrunk toward the first one.
+  let empty_weight = (empty_probability.clamp(0.0, 1.0) * 1000.0).round() as u32;
+  let mut alternatives = vec![];
+  if empty_weight > 0 {
+    alternatives.push((empty_weight, Just(Interval::empty()).boxed()));
+  }
+  if empty_weight < 1000 {
+    alternatives.push((1000 - empty_weight, non_empty.boxed()));
+  }
+  StrategyUnion::new_weighted(alternatives).boxed()
 }
 
-impl<Bound> Bot for Interval<Bound> where
- Bound: Width + Num
+/// Same distribution as the `quickcheck` generator: one interval out of ten is empty and the bounds range over the limits of `Width`.
+#[cfg(feature = "proptest")]
+impl<Bound> ::proptest::arbitrary::Arbitrary for Interval<Bound> where
+ Bound: Width + Num + Debug + 'static,
+ RangeInclusive<Bound>: Strategy<Value=Bound>
 {
-  fn bot() -> Interval<Bound> {
-    Interval::whole()
+  type Parameters = ();
+  type Strategy = BoxedStrategy<Interval<Bound>>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    interval_strategy(<Bound as Width>::min_value()..=<Bound as Width>::max_value(), 0.1)
   }
 }
 
@@ -775,6 +1726,501 @@ mod tests {
     assert_eq!(i1_2, Interval::new(1, 2));
   }
 
+  #[test]
+  fn tuple_test() {
+    assert_eq!(i1_2.as_tuple(), Some((1, 2)));
+    assert_eq!(zero.as_tuple(), Some((0, 0)));
+    assert_eq!(im5_m1.as_tuple(), Some((-5, -1)));
+    assert_eq!(empty.as_tuple(), None);
+    assert_eq!(invalid.as_tuple(), None);
+
+    for i in [zero, one, i1_2, i0_10, im5_m1, im30_m20] {
+      assert_eq!(Interval::from_tuple(i.as_tuple().unwrap()), i);
+    }
+    assert_eq!(Interval::from_tuple((10, -10)), empty);
+    assert_eq!(Interval::from_tuple((10, -10)).as_tuple(), None);
+  }
+
+  #[test]
+  fn to_integer_interval_test() {
+    let min = <i64 as Width>::min_value();
+    let max = <i64 as Width>::max_value();
+    let cases = vec![
+      ((1.2, 4.8), Interval::new(2, 4)),
+      ((1.0, 4.0), Interval::new(1, 4)),
+      ((-4.8, -1.2), Interval::new(-4, -2)),
+      ((-0.5, 0.5), Interval::singleton(0)),
+      ((1.2, 1.8), Interval::empty()),
+      ((3.0, 1.0), Interval::empty()),
+      ((f64::NAN, 1.0), Interval::empty()),
+      ((f64::NEG_INFINITY, f64::INFINITY), Interval::new(min, max)),
+      ((1e30, 1e31), Interval::empty()),
+      ((-1e31, -1e30), Interval::empty()),
+      ((9223372036854775808.0, f64::INFINITY), Interval::empty()),
+      ((f64::NEG_INFINITY, -9223372036854775808.0), Interval::empty()),
+      ((-1e31, 0.5), Interval::new(min, 0)),
+      ((-0.5, 1e31), Interval::new(0, max))
+    ];
+    for ((lb, ub), expected) in cases {
+      let i = Interval::from_f64(lb, ub);
+      assert_eq!(i.to_integer_interval(), expected, "[{}..{}].to_integer_interval()", lb, ub);
+    }
+  }
+
+  #[test]
+  fn floor_ceil_test() {
+    let i = Interval::from_f64(1.2, 4.8);
+    assert_eq!((i.floor().lb, i.floor().ub), (1.0, 4.0));
+    assert_eq!((i.ceil().lb, i.ceil().ub), (2.0, 5.0));
+
+    let sub_unit = Interval::from_f64(1.2, 1.8);
+    assert_eq!((sub_unit.floor().lb, sub_unit.floor().ub), (1.0, 1.0));
+    assert_eq!((sub_unit.ceil().lb, sub_unit.ceil().ub), (2.0, 2.0));
+
+    let empty_f64 = Interval::from_f64(2.5, 2.1);
+    assert!(empty_f64.floor().is_empty_f64());
+    assert!(empty_f64.ceil().is_empty_f64());
+  }
+
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+  enum Weekday { Mon, Tue, Wed, Thu, Fri, Sat, Sun }
+
+  impl Step for Weekday {
+    fn successor(&self) -> Option<Weekday> {
+      use self::Weekday::*;
+      match *self {
+        Mon => Some(Tue), Tue => Some(Wed), Wed => Some(Thu),
+        Thu => Some(Fri), Fri => Some(Sat), Sat => Some(Sun), Sun => None
+      }
+    }
+
+    fn steps_between(start: &Weekday, end: &Weekday) -> Option<usize> {
+      if start > end { None }
+      else { Some(*end as usize - *start as usize) }
+    }
+  }
+
+  #[test]
+  fn into_iter_test() {
+    let mut values = vec![];
+    for x in im5_m1 {
+      values.push(x);
+    }
+    assert_eq!(values, vec![-5, -4, -3, -2, -1]);
+    assert_eq!((&i0_4).into_iter().sum::<i32>(), 10);
+    assert_eq!(empty.into_iter().next(), None);
+    assert_eq!(i0_10.into_iter().size_hint(), (11, Some(11)));
+    assert_eq!(empty.into_iter().size_hint(), (0, Some(0)));
+
+    let mut iter = i0_10.iter();
+    iter.next();
+    iter.next();
+    assert_eq!(iter.size_hint(), (9, Some(9)));
+
+    let top = Interval::new_discrete(250u8, u8::MAX);
+    assert_eq!(top.into_iter().collect::<Vec<_>>(), vec![250, 251, 252, 253, 254, 255]);
+    assert_eq!(top.into_iter().len(), 6);
+    let mut small = Interval::new_discrete(-3i8, 3).into_iter();
+    small.next();
+    assert_eq!(small.len(), 6);
+    assert_eq!(Interval::new_discrete(0u16, u16::MAX).into_iter().len(), 65536);
+    // The size does not fit in a `usize`, hence `StepIter<usize>` is not an `ExactSizeIterator`.
+    assert_eq!(Interval::new_discrete(0usize, usize::MAX).into_iter().size_hint(), (usize::MAX, None));
+  }
+
+  #[test]
+  fn step_test() {
+    use self::Weekday::*;
+    let workdays = Interval::new_discrete(Mon, Fri);
+    let weekend = Interval::new_discrete(Sat, Sun);
+    let midweek = Interval::new_discrete(Tue, Thu);
+    let no_day = Interval::new_discrete(Sun, Mon);
+
+    assert_eq!(midweek.iter().collect::<Vec<_>>(), vec![Tue, Wed, Thu]);
+    assert_eq!(weekend.iter().collect::<Vec<_>>(), vec![Sat, Sun]);
+    assert_eq!(no_day.iter().count(), 0);
+    assert_eq!(workdays.step_count(), Some(5));
+    assert_eq!(Interval::new_discrete(Wed, Wed).step_count(), Some(1));
+    assert_eq!(no_day.step_count(), Some(0));
+    assert!(workdays.is_adjacent_to(&weekend));
+    assert!(weekend.is_adjacent_to(&workdays));
+    assert!(!midweek.is_adjacent_to(&weekend));
+    assert!(!no_day.is_adjacent_to(&workdays));
+
+    assert_eq!(im5_m1.iter().collect::<Vec<_>>(), vec![-5, -4, -3, -2, -1]);
+    assert_eq!(empty.iter().count(), 0);
+    assert_eq!(i0_10.step_count(), Some(i0_10.size() as usize));
+    assert!(i0_4.is_adjacent_to(&i5_10));
+    assert!(!i0_5.is_adjacent_to(&i5_10));
+    let whole_u8: Interval<u8> = Interval::new(0, <u8 as Width>::max_value());
+    assert_eq!(whole_u8.iter().count(), whole_u8.size() as usize);
+    let whole_i8 = Interval::new_discrete(i8::MIN, i8::MAX);
+    assert_eq!(whole_i8.iter().count(), 256);
+    assert_eq!(whole_i8.step_count(), Some(256));
+  }
+
+  #[test]
+  fn lerp_test() {
+    let bounds = |i: Interval<f64>| (i.lb, i.ub);
+    assert_eq!(bounds(i0_10.lerp(&i20_30, 0.0)), (0.0, 10.0));
+    assert_eq!(bounds(i0_10.lerp(&i20_30, 1.0)), (20.0, 30.0));
+    assert_eq!(bounds(i0_10.lerp(&i20_30, 0.5)), (10.0, 20.0));
+    assert_eq!(bounds(i0_10.lerp(&im5_5, 0.5)), (-2.5, 7.5));
+    assert_eq!(bounds(i0_10.lerp(&i20_30, 2.0)), (40.0, 50.0));
+    assert_eq!(bounds(i0_10.lerp(&i20_30, -1.0)), (-20.0, -10.0));
+    assert!(empty.lerp(&i0_10, 0.5).is_empty_f64());
+    assert!(i0_10.lerp(&empty, 0.5).is_empty_f64());
+  }
+
+  #[test]
+  fn scale_about_test() {
+    let cases = vec![
+      (i0_10, 5, 2, Interval::new(-5, 15)),
+      (i0_10, 0, 3, Interval::new(0, 30)),
+      (i0_10, 5, 1, i0_10),
+      // Integer bounds can only shrink down to the center.
+      (i0_10, 5, 0, Interval::singleton(5)),
+      (i5_10, 0, -1, Interval::new(-10, -5)),
+      (i0_10, 2, -2, Interval::new(-14, 6)),
+      (empty, 5, 2, empty)
+    ];
+    for (x, center, factor, expected) in cases {
+      assert_eq!(x.scale_about(center, factor), expected,
+        "{}.scale_about({}, {})", x, center, factor);
+    }
+  }
+
+  #[test]
+  fn max_digit_width_test() {
+    let cases = vec![
+      (zero, 1),
+      (i0_10, 2),
+      (i0_5, 1),
+      (im5_5, 2),
+      (im5_m1, 2),
+      (im30_m20, 3),
+      (Interval::new(-1, 1000), 4),
+      (Interval::new(-1000, 1), 5),
+      (Interval::new(i32::MIN + 1, 0), 11),
+      (empty, 0)
+    ];
+    for (x, expected) in cases {
+      assert_eq!(x.max_digit_width(), expected, "{}.max_digit_width()", x);
+    }
+  }
+
+  #[test]
+  fn rounding_mode_test() {
+    use self::RoundingMode::*;
+    let bounds = |i: Interval<f64>| (i.lb, i.ub);
+    let a = Interval::from_f64(0.1, 0.7);
+    let b = Interval::from_f64(-0.2, 0.3);
+    let empty_f64 = Interval::from_f64(1.0, 0.0);
+    type Op = fn(&Interval<f64>, &Interval<f64>, RoundingMode) -> Interval<f64>;
+    let ops: Vec<Op> = vec![Interval::add_rounded, Interval::sub_rounded, Interval::mul_rounded];
+    for op in ops {
+      let nearest = op(&a, &b, Nearest);
+      let outward = op(&a, &b, RoundingMode::default());
+      let inward = op(&a, &b, Inward);
+      assert!(outward.lb < nearest.lb && nearest.ub < outward.ub);
+      assert!(nearest.lb < inward.lb && inward.ub < nearest.ub);
+      assert!(op(&a, &empty_f64, Outward).is_empty_f64());
+      assert!(op(&empty_f64, &b, Nearest).is_empty_f64());
+    }
+    assert_eq!(bounds(a.add_rounded(&b, Nearest)), (0.1 - 0.2, 0.7 + 0.3));
+    assert_eq!(bounds(a.sub_rounded(&b, Nearest)), (0.1 - 0.3, 0.7 + 0.2));
+    assert_eq!(bounds(a.mul_rounded(&b, Nearest)), (0.7 * -0.2, 0.7 * 0.3));
+    // The sum of the nearest floating point numbers to 0.1 and 0.2 is not representable.
+    let sum = Interval::from_f64(0.1, 0.1).add_rounded(&Interval::from_f64(0.2, 0.2), Outward);
+    assert_eq!(bounds(sum), ((0.1f64 + 0.2).next_down(), (0.1f64 + 0.2).next_up()));
+    assert!(Interval::from_f64(0.1, 0.1).add_rounded(&Interval::from_f64(0.2, 0.2), Inward).is_empty_f64());
+    let unbounded = Interval::from_f64(0.0, 1.0).mul_rounded(&Interval::from_f64(1.0, f64::INFINITY), Outward);
+    assert_eq!(bounds(unbounded), (0.0f64.next_down(), f64::INFINITY));
+  }
+
+  #[test]
+  fn to_f32_interval_test() {
+    let cases = vec![
+      (0.1, 0.2),
+      (-0.3, 1.0 / 3.0),
+      (1.0, 2.5),
+      (-1e300, 1e300),
+      (16777217.0, 16777217.0)
+    ];
+    for (lb, ub) in cases {
+      let i = Interval::from_f64(lb, ub).to_f32_interval();
+      assert!((i.lb as f64) <= lb && (i.ub as f64) >= ub,
+        "[{}..{}] is not enclosed in [{}..{}]", lb, ub, i.lb, i.ub);
+    }
+
+    // Exactly representable bounds are left unchanged.
+    let exact = Interval::from_f64(1.0, 2.5).to_f32_interval();
+    assert_eq!((exact.lb, exact.ub), (1.0, 2.5));
+    // 0.1 is not representable in `f32`, the nearest `f32` is above it.
+    let rounded = Interval::from_f64(0.1, 0.1).to_f32_interval();
+    assert!((rounded.lb as f64) < 0.1 && (rounded.ub as f64) > 0.1);
+    assert_eq!(rounded.lb.next_up(), rounded.ub);
+    let huge = Interval::from_f64(-1e300, 1e300).to_f32_interval();
+    assert_eq!((huge.lb, huge.ub), (f32::NEG_INFINITY, f32::INFINITY));
+    let empty_f32 = Interval::from_f64(1.0, 0.0).to_f32_interval();
+    assert!(empty_f32.lb > empty_f32.ub);
+  }
+
+  #[test]
+  fn contains_interval_test() {
+    // (container, contained, contains_interval, strictly_contains)
+    let cases = vec![
+      (i0_10, i0_10, true, false),
+      (i0_10, i1_2, true, true),
+      (i0_10, i0_5, true, true),
+      (i0_10, i0_15, false, false),
+      (i0_10, im5_5, false, false),
+      (i0_10, i20_30, false, false),
+      (i0_10, empty, true, true),
+      (zero, empty, true, true),
+      (empty, empty, true, false),
+      (empty, zero, false, false)
+    ];
+    for (x, y, contains, strict) in cases {
+      assert_eq!(x.contains_interval(&y), contains, "{}.contains_interval({})", x, y);
+      assert_eq!(x.strictly_contains(&y), strict, "{}.strictly_contains({})", x, y);
+    }
+  }
+
+  #[test]
+  fn split_off_test() {
+    // (x, at, shortened x, returned part)
+    let cases = vec![
+      (i0_10, 5, i0_4, i5_10),
+      (i0_10, 1, zero, i1_10),
+      (i0_10, 10, i0_9, ten),
+      (i0_10, 0, empty, i0_10),
+      (i0_10, -5, empty, i0_10),
+      (i0_10, 11, i0_10, empty),
+      (i0_10, 20, i0_10, empty),
+      (empty, 0, empty, empty)
+    ];
+    for (x, at, expected_x, expected_right) in cases {
+      let mut shortened = x;
+      let right = shortened.split_off(at);
+      assert_eq!(shortened, expected_x, "{}.split_off({}) shortened part", x, at);
+      assert_eq!(right, expected_right, "{}.split_off({}) returned part", x, at);
+    }
+  }
+
+  #[test]
+  fn identities_test() {
+    assert_eq!(Interval::zero(), zero);
+    assert_eq!(Interval::one(), one);
+    for iv in [empty, zero, one, i0_10, im5_5, im30_m20, i20_30] {
+      assert_eq!(iv + Interval::zero(), iv, "{} + [0..0]", iv);
+      assert_eq!(Interval::zero() + iv, iv, "[0..0] + {}", iv);
+      assert_eq!(iv * Interval::one(), iv, "{} * [1..1]", iv);
+      assert_eq!(Interval::one() * iv, iv, "[1..1] * {}", iv);
+    }
+  }
+
+  #[test]
+  fn iter_capped_test() {
+    let small: Interval<i64> = Interval::new(-2, 2);
+    assert_eq!(small.iter_capped(100).collect::<Vec<_>>(), vec![-2, -1, 0, 1, 2]);
+    assert_eq!(small.iter_capped(2).collect::<Vec<_>>(), vec![-2, -1]);
+    assert_eq!(small.iter_capped(0).count(), 0);
+
+    let huge = Interval::<i64>::whole();
+    let first: Vec<i64> = huge.iter_capped(3).collect();
+    assert_eq!(first, vec![i64::MIN + 1, i64::MIN + 2, i64::MIN + 3]);
+    assert_eq!(huge.iter_capped(100).count(), 100);
+    assert_eq!(Interval::<i64>::empty().iter_capped(100).count(), 0);
+  }
+
+  #[test]
+  fn align_to_grid_test() {
+    let cases = vec![
+      (i0_10, 0, 5, i0_10),
+      (i0_10, 0, 1, i0_10),
+      (Interval::new(-10, 20), 0, 10, Interval::new(-10, 20)),
+      (Interval::new(1, 9), 0, 5, i0_10),
+      (Interval::new(-7, -2), 0, 5, Interval::new(-10, 0)),
+      (Interval::new(3, 4), 0, 4, Interval::new(0, 4)),
+      (Interval::new(3, 5), 0, 4, Interval::new(0, 8)),
+      (i0_10, 1, 4, Interval::new(-3, 13)),
+      (i0_10, 0, 3, Interval::new(0, 12)),
+      (zero, 0, 7, zero),
+      (Interval::singleton(2), 0, 7, Interval::new(0, 7)),
+      (empty, 0, 5, empty)
+    ];
+    for (x, origin, step, expected) in cases {
+      assert_eq!(x.align_to_grid(origin, step), expected,
+        "{}.align_to_grid({}, {})", x, origin, step);
+    }
+  }
+
+  #[test]
+  fn probability_within_test() {
+    let cases = vec![
+      (i0_10, i0_10, 1.0),
+      (i1_2, i0_10, 1.0),
+      (i0_9, i0_4, 0.5),
+      (i0_9, Interval::new(5, 20), 0.5),
+      (i0_10, i20_30, 0.0),
+      (i0_10, empty, 0.0),
+      (empty, i0_10, 0.0),
+      (im5_5, zero, 1.0 / 11.0)
+    ];
+    for (x, sub, expected) in cases {
+      assert_eq!(x.probability_within(&sub), expected, "{}.probability_within({})", x, sub);
+    }
+    let whole: Interval<i64> = Interval::whole();
+    assert_eq!(whole.probability_within(&Interval::new(0, i64::MAX)), 0.5);
+  }
+
+  #[test]
+  fn intersect_all_test() {
+    let cases = vec![
+      (vec![], Interval::whole()),
+      (vec![i0_10], i0_10),
+      (vec![i0_10, im5_5, i0_15], i0_5),
+      (vec![i0_10, im5_5, i5_10], Interval::singleton(5)),
+      (vec![i0_10, i20_30, im5_5], empty),
+      (vec![i0_10, empty], empty)
+    ];
+    for (intervals, expected) in cases {
+      assert_eq!(Interval::intersect_all(&intervals), expected, "intersect_all({:?})", intervals);
+    }
+  }
+
+  #[test]
+  fn coverage_of_test() {
+    let cases = vec![
+      (i0_10, i0_10, 1.0),
+      (i0_15, i0_10, 1.0),
+      (i0_4, i0_9, 0.5),
+      (i0_10, i0_4, 1.0),
+      (i1_2, i0_9, 0.2),
+      (i20_30, i0_10, 0.0),
+      (empty, i0_10, 0.0),
+      (i0_10, empty, 0.0)
+    ];
+    for (x, reference, expected) in cases {
+      assert_eq!(x.coverage_of(&reference), expected, "{}.coverage_of({})", x, reference);
+    }
+  }
+
+  #[cfg(feature = "rand")]
+  #[test]
+  fn random_subinterval_test() {
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    let mut rng = StdRng::seed_from_u64(42);
+    for x in [zero, i0_10, im5_5, im30_m20, Interval::whole()] {
+      for _ in 0..1000 {
+        let sub = x.random_subinterval(&mut rng);
+        assert!(!sub.is_empty(), "{}.random_subinterval() is empty", x);
+        assert!(sub.is_subset(&x), "{} is not a subset of {}", sub, x);
+      }
+    }
+    assert!(empty.random_subinterval(&mut rng).is_empty());
+    let drawn = (0..1000).map(|_| i0_2.random_subinterval(&mut rng))
+      .collect::<Vec<_>>();
+    for i in [i0_1, i0_2, i1_2, zero, one, Interval::singleton(2)] {
+      assert!(drawn.contains(&i), "{} was never drawn", i);
+    }
+  }
+
+  #[test]
+  fn with_bounds_test() {
+    assert_eq!(i0_10.with_lower(5), i5_10);
+    assert_eq!(i0_10.with_lower(-5), Interval::new(-5, 10));
+    assert_eq!(i0_10.with_upper(4), i0_4);
+    assert_eq!(i0_10.with_upper(15), i0_15);
+    assert_eq!(i0_10.with_lower(10), ten);
+    assert_eq!(i0_10.with_lower(11), empty);
+    assert!(i0_10.with_upper(-1).is_empty());
+    assert_eq!(i0_10.with_lower(1).with_upper(2), i1_2);
+    assert_eq!(i0_10.with_upper(2).with_lower(1), i1_2);
+    assert_eq!(i0_10.with_lower(20).with_upper(30), empty);
+    assert_eq!(empty.with_lower(0), empty);
+    assert_eq!(empty.with_upper(10), empty);
+  }
+
+  #[test]
+  fn overflowing_add_test() {
+    let max = <i32 as Width>::max_value();
+    let min = <i32 as Width>::min_value();
+    let cases = vec![
+      (i0_10, i20_30, (Interval::new(20, 40), false)),
+      (im5_5, i0_10, (Interval::new(-5, 15), false)),
+      (Interval::new(0, max - 5), i0_10, (Interval::new(0, max), true)),
+      (Interval::new(max - 5, max), i0_10, (Interval::new(max - 5, max), true)),
+      (Interval::new(max - 5, max), i20_30, (Interval::new(max, max), true)),
+      (Interval::new(min, 0), im5_5, (Interval::new(min, 5), true)),
+      (Interval::new(min + 10, 0), im5_5, (Interval::new(min + 5, 5), false)),
+      (Interval::new(0, max - 10), i0_10, (Interval::new(0, max), false)),
+      (empty, i0_10, (empty, false))
+    ];
+    for (x, y, expected) in cases {
+      assert_eq!(x.overflowing_add(&y), expected, "{}.overflowing_add({})", x, y);
+    }
+
+    // The unsigned `Width::max_value()` is one below the primitive maximum.
+    let u: Interval<u8> = Interval::new(250, 253);
+    assert_eq!(u.overflowing_add(&Interval::new(1, 1)), (Interval::new(251, 254), false));
+    assert_eq!(u.overflowing_add(&Interval::new(1, 2)), (Interval::new(251, 254), true));
+    assert_eq!(u.overflowing_add(&Interval::new(10, 10)), (Interval::new(254, 254), true));
+  }
+
+  #[test]
+  fn clamp_to_type_test() {
+    for x in [empty, zero, i0_10, im5_5, Interval::whole()] {
+      assert_eq!(x.clamp_to_type(), x);
+    }
+
+    // Bounds from the saturating arithmetic of `i32` and `u8`, built without `Interval::new` since they exceed the limits of `Width`.
+    let saturated = Interval { lb: (-10i32).saturating_sub(i32::MAX), ub: 10i32.saturating_add(i32::MAX) };
+    assert_eq!(saturated.lb, i32::MIN);
+    let clamped = saturated.clamp_to_type();
+    assert_eq!(clamped, Interval::whole());
+    assert_eq!(clamped.size(), u32::MAX);
+
+    let saturated = Interval { lb: 200u8, ub: 200u8.saturating_add(100) };
+    assert_eq!(saturated.clamp_to_type(), Interval::new(200, <u8 as Width>::max_value()));
+    assert_eq!(saturated.clamp_to_type().size(), 55);
+  }
+
+  #[test]
+  fn count_matching_test() {
+    for lb in -20i64..20 {
+      for ub in (lb - 1)..20 {
+        let x = Interval::new(lb, ub);
+        for k in 1..6 {
+          let brute_force = (lb..=ub).filter(|v| v % k == 0).count();
+          assert_eq!(x.count_matching(|v| v % k == 0), brute_force,
+            "{}.count_matching(divisible by {})", x, k);
+        }
+      }
+    }
+    assert_eq!(Interval::<i64>::new(0, 9).count_matching(|v| v > 6), 3);
+    assert_eq!(Interval::<i64>::empty().count_matching(|_| true), 0);
+  }
+
+  #[test]
+  fn try_bounded_test() {
+    assert_eq!(i0_10.try_lower(), Some(0));
+    assert_eq!(i0_10.try_upper(), Some(10));
+    assert_eq!(im5_m1.try_lower(), Some(-5));
+    assert_eq!(zero.try_upper(), Some(0));
+    assert_eq!(empty.try_lower(), None);
+    assert_eq!(empty.try_upper(), None);
+    assert_eq!(invalid.try_lower(), None);
+  }
+
+  #[test]
+  #[should_panic(expected = "Cannot access lower bound on empty interval.")]
+  fn lower_of_empty_test() {
+    empty.lower();
+  }
+
   #[test]
   fn equality_test() {
     assert_eq!(empty, empty);
@@ -801,6 +2247,57 @@ mod tests {
     assert_eq!(whole_u32.size(), u32::max_value());
   }
 
+  #[test]
+  fn size_singleton_test() {
+    let min = <i32 as Width>::min_value();
+    let max = <i32 as Width>::max_value();
+    let umax = <u32 as Width>::max_value();
+
+    assert_eq!(Interval::singleton(min).size(), 1);
+    assert_eq!(Interval::singleton(max).size(), 1);
+    assert_eq!(Interval::singleton(0u32).size(), 1);
+    assert_eq!(Interval::singleton(umax).size(), 1);
+    assert_eq!(Interval::<u32>::empty().size(), 0);
+    assert!(one.is_singleton());
+    assert!(!empty.is_singleton());
+    assert!(!invalid.is_singleton());
+  }
+
+  #[test]
+  fn checked_size_test() {
+    let whole_i64: Interval<i64> = Interval::whole();
+    let whole_u128: Interval<u128> = Interval::whole();
+
+    assert_eq!(empty.checked_size(), Some(0));
+    assert_eq!(zero.checked_size(), Some(1));
+    assert_eq!(i0_10.checked_size(), Some(11));
+    assert_eq!(whole_i64.checked_size(), Some(u64::MAX as u128));
+    assert_eq!(whole_u128.checked_size(), Some(u128::MAX));
+  }
+
+  #[test]
+  fn size_u128_test() {
+    let whole_i64: Interval<i64> = Interval::whole();
+    let full_i64 = Interval::new_discrete(i64::MIN, i64::MAX);
+    let full_u64 = Interval::new_discrete(0, u64::MAX);
+    let full_i8 = Interval::new_discrete(i8::MIN, i8::MAX);
+    let sut = vec![
+      (empty.size_u128(), 0),
+      (zero.size_u128(), 1),
+      (i0_10.size_u128(), 11),
+      (whole_i64.size_u128(), (1u128 << 64) - 1),
+      (full_i64.size_u128(), 1u128 << 64),
+      (full_u64.size_u128(), 1u128 << 64),
+      (full_i8.size_u128(), 256),
+      (Interval::new_discrete(i64::MAX, i64::MAX).size_u128(), 1),
+      (Interval::new_discrete(i64::MAX, i64::MIN).size_u128(), 0),
+      (Interval::new_discrete(u128::MAX - 1, u128::MAX).size_u128(), 2)
+    ];
+    for (actual, expected) in sut {
+      assert_eq!(actual, expected);
+    }
+  }
+
   #[test]
   fn contains_test() {
     assert!(i1_2.contains(&1));
@@ -821,6 +2318,26 @@ mod tests {
     assert!(!invalid.contains(&11));
   }
 
+  #[test]
+  fn contains_exclusive_test() {
+    assert!(!i0_10.contains_exclusive(&0));
+    assert!(!i0_10.contains_exclusive(&10));
+    assert!(i0_10.contains_exclusive(&1));
+    assert!(i0_10.contains_exclusive(&5));
+    assert!(i0_10.contains_exclusive(&9));
+    assert!(!i0_10.contains_exclusive(&-1));
+    assert!(!i0_10.contains_exclusive(&11));
+
+    assert!(!i0_1.contains_exclusive(&0));
+    assert!(!i0_1.contains_exclusive(&1));
+    assert!(i0_2.contains_exclusive(&1));
+    assert!(!zero.contains_exclusive(&0));
+
+    assert!(!empty.contains_exclusive(&0));
+    assert!(!empty.contains_exclusive(&1));
+    assert!(!invalid.contains_exclusive(&0));
+  }
+
   #[test]
   fn is_subset_test() {
     let cases = vec![
@@ -995,6 +2512,25 @@ mod tests {
     }
   }
 
+  #[test]
+  fn intersect_nonempty_test() {
+    let sym_cases = vec![
+      (i0_5, i5_10,         Some(5.to_interval())),
+      (im5_5, i0_10,        Some(i0_5)),
+      (i1_2, i0_10,         Some(i1_2)),
+      (i0_4, i5_10,         None),
+      (i0_10, i20_30,       None),
+      (empty, i0_10,        None),
+      (invalid, i1_2,       None),
+      (empty, empty,        None)
+    ];
+
+    for (x,y,r) in sym_cases.into_iter() {
+      assert!(x.intersect_nonempty(&y) == r, "{:?} intersect_nonempty {:?} is not equal to {:?}", x, y, r);
+      assert!(y.intersect_nonempty(&x) == r, "{:?} intersect_nonempty {:?} is not equal to {:?}", y, x, r);
+    }
+  }
+
   #[test]
   fn intersection_value_optional_test() {
     let cases = vec![
@@ -1400,7 +2936,8 @@ mod tests {
       (zero, i0_10,       ((-10,0), (0,10))),
       (i1_2, i0_10,       ((-9,2), (-2, 9))),
       (im5_10, i0_10,     ((-15,10), (-10, 15))),
-      (im5_10, im30_m20,  ((15,40), (-40,-15)))
+      (im5_10, im30_m20,  ((15,40), (-40,-15))),
+      (i5_10, i1_2,       ((3,9), (-9,-3)))
     ];
 
     for &(x,y,r) in &cases {
@@ -1449,6 +2986,335 @@ mod tests {
     }
   }
 
+  #[test]
+  fn checked_arithmetic_test() {
+    let whole = Interval::<i32>::whole();
+    let two = Interval::singleton(2);
+    let max = <i32 as Width>::max_value();
+    let min = <i32 as Width>::min_value();
+    let near_max = Interval::new(max - 5, max);
+
+    assert_eq!(whole.checked_mul(&two), None);
+    assert_eq!(two.checked_mul(&whole), None);
+    assert_eq!(whole.saturating_mul(&two), whole);
+    assert_eq!(Interval::new(-1, 1).saturating_mul(&near_max), Interval::new(-max, max));
+    assert_eq!(Interval::new(min, -1).saturating_mul(&Interval::new(-2, -1)), Interval::new(1, max));
+    assert_eq!(near_max.checked_add(&one), None);
+    assert_eq!(near_max.checked_add(&zero), Some(near_max));
+    assert_eq!(near_max.checked_sub(&Interval::new(-1, 0)), None);
+    assert_eq!(Interval::new(min, 0).checked_sub(&one), None);
+    // `i32::MIN` is outside the limits of `Width`.
+    assert_eq!(Interval::new(min, 0).checked_add(&Interval::new(-1, 0)), None);
+
+    assert_eq!(im5_10.checked_mul(&im30_m20), Some(im5_10 * im30_m20));
+    assert_eq!(i1_2.checked_add(&i0_10), Some(i1_2 + i0_10));
+    assert_eq!(i1_2.checked_sub(&i0_10), Some(i1_2 - i0_10));
+    assert_eq!(empty.checked_mul(&whole), Some(empty));
+    assert_eq!(empty.saturating_mul(&whole), empty);
+  }
+
+  #[test]
+  fn div_test() {
+    // For each cases (x, y, res)
+    // * x and y are the values
+    // * res is the result of `x / y`
+    let cases = vec![
+      (empty, i1_2,           empty),
+      (i1_2, empty,           empty),
+      (invalid, i1_2,         empty),
+      (i0_10, zero,           empty),
+      (zero, i1_2,            zero),
+      (one, (2,2).to_interval(), i0_1),
+      ((10,20).to_interval(), (2,5).to_interval(),   (2,10).to_interval()),
+      ((10,20).to_interval(), (3,7).to_interval(),   (1,7).to_interval()),
+      ((-20,-10).to_interval(), (2,5).to_interval(), (-10,-2).to_interval()),
+      (im5_10, (2,2).to_interval(),                  (-3,5).to_interval()),
+      (im5_10, im30_m20,                             (-1,1).to_interval()),
+      // The divisor contains zero.
+      ((10,20).to_interval(), (0,5).to_interval(),   (2,20).to_interval()),
+      ((10,20).to_interval(), (-5,0).to_interval(),  (-20,-2).to_interval()),
+      ((10,20).to_interval(), (-2,5).to_interval(),  (-20,20).to_interval()),
+      (im5_10, im5_5,                                (-10,10).to_interval())
+    ];
+
+    for &(x,y,r) in &cases {
+      assert!(x / y == r, "{:?} / {:?} is not equal to {:?}", x, y, r);
+    }
+
+    let unsigned = Interval::new(10u32, 20) / Interval::new(0, 5);
+    assert_eq!(unsigned, Interval::new(2, 20));
+    let whole = Interval::<i32>::whole();
+    assert_eq!(whole / Interval::new(-1, -1), whole);
+  }
+
+  #[test]
+  fn partial_ord_test() {
+    let nan = f64::NAN;
+    let a = Interval::from_f64(0.0, 2.5);
+    let b = Interval::from_f64(1.5, 4.0);
+    let c = Interval::from_f64(3.0, 4.0);
+    let empty_f64 = Interval::from_f64(1.0, 0.0);
+    let nan_bound = Interval { lb: nan, ub: 1.0 };
+
+    assert!(a.contains_partial(&0.0) && a.contains_partial(&2.5) && a.contains_partial(&1.2));
+    assert!(!a.contains_partial(&2.6));
+    assert!(!a.contains_partial(&nan));
+    assert!(!empty_f64.contains_partial(&0.5));
+    assert!(!nan_bound.contains_partial(&0.0));
+
+    assert!(nan_bound.is_empty_partial() && empty_f64.is_empty_partial());
+    assert!(!a.is_empty_partial());
+    assert!(Interval::from_f64(nan, 1.0).is_empty_partial());
+
+    assert!(a.overlap_partial(&b) && b.overlap_partial(&a));
+    assert!(!a.overlap_partial(&c));
+    assert!(!a.overlap_partial(&empty_f64));
+    assert!(!a.overlap_partial(&nan_bound) && !nan_bound.overlap_partial(&a));
+
+    let ab = a.intersection_partial(&b).unwrap();
+    assert_eq!((ab.lb, ab.ub), (1.5, 2.5));
+    assert!(a.intersection_partial(&c).unwrap().is_empty_partial());
+    assert!(a.intersection_partial(&empty_f64).unwrap().is_empty_partial());
+    assert!(nan_bound.intersection_partial(&a).unwrap().is_empty_partial());
+
+    // Agrees with the totally ordered operations.
+    assert!(i0_10.contains_partial(&5) && !i0_10.contains_partial(&11));
+    assert_eq!(i0_10.intersection_partial(&im5_5), Some(i0_5));
+    assert!(empty.is_empty_partial() && !i0_10.overlap_partial(&empty));
+  }
+
+  #[test]
+  fn convex_hull_test() {
+    use interval_set::ToIntervalSet;
+    assert_eq!(i0_10.convex_hull(), i0_10);
+    assert!(empty.convex_hull().is_empty());
+    assert_eq!(Optional::singleton(im5_10).convex_hull(), im5_10);
+    let gappy = vec![(0,4),(6,10)].to_interval_set();
+    assert_eq!(Optional::singleton(gappy).convex_hull(), i0_10);
+    assert!(Optional::<Interval<i32>>::empty().convex_hull().is_empty());
+    assert!(Optional::singleton(empty).convex_hull().is_empty());
+  }
+
+  #[cfg(feature = "proptest")]
+  #[test]
+  fn proptest_strategy_test() {
+    use proptest::test_runner::{TestRunner, TestError, TestCaseError};
+    use proptest::arbitrary::any;
+    TestRunner::deterministic().run(&interval_strategy(-100..=100, 0.2), |i| {
+      assert!(i.is_empty() || (i.lb <= i.ub && i.lb >= -100 && i.ub <= 100));
+      Ok(())
+    }).unwrap();
+    TestRunner::deterministic().run(&interval_strategy(0u8..=u8::MAX, 0.0), |i| {
+      assert!(!i.is_empty() && i.ub <= <u8 as Width>::max_value());
+      Ok(())
+    }).unwrap();
+    TestRunner::deterministic().run(&any::<Interval<i8>>(), |i| {
+      assert!(i.is_empty() || i.lb >= <i8 as Width>::min_value());
+      Ok(())
+    }).unwrap();
+
+    // Failing cases are minimized toward the empty interval and then toward narrower bounds.
+    match TestRunner::deterministic().run(&interval_strategy(-1000..=1000, 0.5), |_| Err(TestCaseError::fail("always"))) {
+      Err(TestError::Fail(_, i)) => assert!(i.is_empty()),
+      _ => panic!("the property should fail")
+    }
+    match TestRunner::deterministic().run(&interval_strategy(-1000..=1000, 0.0), |i| {
+      if i.size() >= 10u32 { Err(TestCaseError::fail("too wide")) } else { Ok(()) }
+    }) {
+      Err(TestError::Fail(_, i)) => assert_eq!(i.size(), 10u32, "{} is not minimal", i),
+      _ => panic!("the property should fail")
+    }
+  }
+
+  #[test]
+  fn distance_test() {
+    // For each cases (x, y, res): res is the distance between x and y, and between y and x.
+    let cases = vec![
+      (i0_10, i0_10,            Some(0)),
+      (i0_10, i1_2,             Some(0)),
+      (i0_5, i5_10,             Some(0)),
+      (i0_4, i5_10,             Some(0)),
+      (zero, one,               Some(0)),
+      (i1_2, i5_10,             Some(2)),
+      (im5_m1, i1_2,            Some(1)),
+      (im30_m20, i6_10,         Some(25)),
+      (empty, i0_10,            None),
+      (invalid, empty,          None)
+    ];
+    for &(x, y, r) in &cases {
+      assert_eq!(x.distance(&y), r, "distance({}, {})", x, y);
+      assert_eq!(y.distance(&x), r, "distance({}, {})", y, x);
+    }
+    let max = <i32 as Width>::max_value();
+    let extremes = (Interval::singleton(-max), Interval::singleton(max));
+    assert_eq!(extremes.0.distance(&extremes.1), Some(u32::MAX - 2));
+
+    let some_i1_2 = Optional::singleton(i1_2);
+    assert_eq!(some_i1_2.distance(&Optional::singleton(i5_10)), Some(2));
+    assert_eq!(some_i1_2.distance(&Optional::empty()), None);
+    assert_eq!(some_i1_2.distance(&Optional::singleton(empty)), None);
+  }
+
+  #[test]
+  fn display_test() {
+    assert_eq!(format!("{}", empty), "{}");
+    assert_eq!(format!("{}", invalid), "{}");
+    assert_eq!(format!("{}", zero), "[0..0]");
+    assert_eq!(format!("{}", i0_10), "[0..10]");
+    assert_eq!(format!("{}", im5_m1), "[-5..-1]");
+  }
+
+  #[test]
+  fn contains_progression_test() {
+    let i0_100 = Interval::new(0i64, 100);
+    let cases = vec![
+      (0, 10, 11, true),
+      (0, 10, 12, false),
+      (5, 0, 1000, true),
+      (101, 0, 3, false),
+      (100, -25, 5, true),
+      (100, -25, 6, false),
+      (-1, 1, 3, false),
+      (50, 7, 1, true),
+      (500, 7, 0, true),
+      (0, i64::MAX, 2, false),
+      (100, i64::MIN, usize::MAX, false)
+    ];
+    for (start, step, count, expected) in cases {
+      assert_eq!(i0_100.contains_progression(start, step, count), expected,
+        "contains_progression({}, {}, {})", start, step, count);
+    }
+    assert!(!Interval::<i64>::empty().contains_progression(0, 1, 1));
+    assert!(Interval::<i64>::empty().contains_progression(0, 1, 0));
+    assert!(Interval::<i64>::whole().contains_progression(0, 1, usize::MAX / 2));
+  }
+
+  #[test]
+  fn bisect_to_size_test() {
+    let leaves = |x: Interval<i64>, target| -> Vec<(i64, i64)> {
+      x.bisect_to_size(target).iter().map(|l| (l.lower(), l.upper())).collect()
+    };
+    assert_eq!(leaves(Interval::new(0, 15), 4), vec![(0, 3), (4, 7), (8, 11), (12, 15)]);
+    assert_eq!(leaves(Interval::new(0, 9), 3), vec![(0, 2), (3, 4), (5, 7), (8, 9)]);
+    assert_eq!(Interval::new(-3i64, 3).bisect_to_size(7), vec![Interval::new(-3, 3)]);
+    assert_eq!(Interval::new(5i64, 5).bisect_to_size(1), vec![Interval::singleton(5)]);
+    assert!(Interval::<i64>::empty().bisect_to_size(2).is_empty());
+
+    let cases = vec![(Interval::new(-50i64, 49), 7), (Interval::new(3, 1003), 1), (Interval::whole(), i64::MAX / 3)];
+    for (x, target) in cases {
+      let leaves = x.bisect_to_size(target);
+      assert_eq!(leaves.first().unwrap().lower(), x.lower());
+      assert_eq!(leaves.last().unwrap().upper(), x.upper());
+      for pair in leaves.windows(2) {
+        assert_eq!(pair[0].upper() + 1, pair[1].lower());
+      }
+      assert!(leaves.iter().all(|l| !l.is_empty() && l.size_u128() <= target as u128));
+    }
+  }
+
+  #[test]
+  fn intersect_progression_test() {
+    use interval_set::ToIntervalSet;
+    let w1_10 = Interval::new(1i64, 10);
+    let cases = vec![
+      (w1_10, 0, 3, vec![(3,3),(6,6),(9,9)]),
+      (w1_10, 0, -3, vec![(3,3),(6,6),(9,9)]),
+      (w1_10, -20, 7, vec![(1,1),(8,8)]),
+      (w1_10, 100, 4, vec![(4,4),(8,8)]),
+      (Interval::new(5, 6), 0, 4, vec![]),
+      (Interval::new(5, 6), 7, 0, vec![]),
+      (w1_10, 7, 0, vec![(7,7)]),
+      (w1_10, 42, 1, vec![(1,10)]),
+      (w1_10, 0, -1, vec![(1,10)]),
+      (Interval::empty(), 0, 2, vec![]),
+      (Interval::whole(), 0, i64::MAX, vec![(-i64::MAX,-i64::MAX),(0,0),(i64::MAX,i64::MAX)])
+    ];
+    for (x, start, step, expected) in cases {
+      assert_eq!(x.intersect_progression(start, step), expected.to_interval_set(),
+        "{}.intersect_progression({}, {})", x, start, step);
+    }
+  }
+
+  #[test]
+  fn expanding_windows_test() {
+    let windows = |x: Interval<i32>, center, step| -> Vec<(i32, i32)> {
+      x.expanding_windows(center, step).map(|w| (w.lower(), w.upper())).collect()
+    };
+    assert_eq!(windows(i0_10, 5, 2), vec![(5,5),(3,7),(1,9),(0,10)]);
+    assert_eq!(windows(i0_10, 5, 5), vec![(5,5),(0,10)]);
+    assert_eq!(windows(i0_10, 1, 3), vec![(1,1),(0,4),(0,7),(0,10)]);
+    assert_eq!(windows(i0_10, 10, 4), vec![(10,10),(6,10),(2,10),(0,10)]);
+    assert_eq!(windows(i0_10, 3, 100), vec![(3,3),(0,10)]);
+    assert_eq!(windows(zero, 0, 1), vec![(0,0)]);
+    assert!(windows(i0_10, 11, 1).is_empty());
+    assert!(windows(empty, 0, 1).is_empty());
+
+    let whole_i8 = Interval::<i8>::whole();
+    let last = whole_i8.expanding_windows(100, 50).last();
+    assert_eq!(last, Some(whole_i8));
+    assert_eq!(whole_i8.expanding_windows(100, 50).count(), 6);
+  }
+
+  #[test]
+  #[should_panic]
+  fn expanding_windows_zero_step_test() {
+    let _ = i0_10.expanding_windows(5, 0);
+  }
+
+  #[test]
+  fn golden_split_test() {
+    let cases = vec![
+      ((0, 100), (38, 62)),
+      ((-10, 10), (-2, 2)),
+      ((0, 3), (1, 2)),
+      ((0, 2), (1, 1)),
+      ((0, 1), (0, 1)),
+      ((5, 5), (5, 5))
+    ];
+    for ((lb, ub), expected) in cases {
+      assert_eq!(Interval::new(lb, ub).golden_split(), expected, "golden_split([{}..{}])", lb, ub);
+    }
+    let (a, b) = Interval::<i64>::whole().golden_split();
+    assert!(a < 0 && 0 < b && a == -b);
+  }
+
+  #[test]
+  fn to_mask_test() {
+    let t = true;
+    let f = false;
+    let i2_4 = Interval::new(2i64, 4);
+    assert_eq!(i2_4.to_mask(0, 6), Some(vec![f, f, t, t, t, f]));
+    assert_eq!(i2_4.to_mask(2, 3), Some(vec![t, t, t]));
+    assert_eq!(i2_4.to_mask(3, 4), Some(vec![t, t, f, f]));
+    assert_eq!(i2_4.to_mask(-1, 4), Some(vec![f, f, f, t]));
+    assert_eq!(i2_4.to_mask(10, 3), Some(vec![f, f, f]));
+    assert_eq!(i2_4.to_mask(0, 0), Some(vec![]));
+    assert_eq!(Interval::<i64>::empty().to_mask(0, 2), Some(vec![f, f]));
+    assert_eq!(Interval::<i64>::whole().to_mask(-1, 2), Some(vec![t, t]));
+    assert_eq!(i2_4.to_mask(i64::MAX - 2, 10), None);
+    assert_eq!(i2_4.to_mask(i64::MAX - 1, 2), Some(vec![f, f]));
+    assert_eq!(i2_4.to_mask(i64::MIN, 2), None);
+    assert_eq!(i2_4.to_mask(0, usize::MAX), None);
+  }
+
+  #[test]
+  fn display_unbounded_test() {
+    let min = <i32 as Width>::min_value();
+    let max = <i32 as Width>::max_value();
+    let whole: Interval<i32> = Interval::whole();
+
+    assert_eq!(format!("{}", Interval::new(min, 10).display_unbounded()), "[-∞..10]");
+    assert_eq!(format!("{}", Interval::new(-10, max).display_unbounded()), "[-10..+∞]");
+    assert_eq!(format!("{}", whole.display_unbounded()), "[-∞..+∞]");
+    assert_eq!(format!("{}", im5_10.display_unbounded()), "[-5..10]");
+    assert_eq!(format!("{}", zero.display_unbounded()), "[0..0]");
+    assert_eq!(format!("{}", empty.display_unbounded()), "{}");
+
+    let whole_u32: Interval<u32> = Interval::whole();
+    assert_eq!(format!("{}", Interval::new(0u32, 10).display_unbounded()), "[0..10]");
+    assert_eq!(format!("{}", whole_u32.display_unbounded()), "[0..+∞]");
+  }
+
   #[test]
   fn test_lattice() {
     use gcollections::ops::lattice::test::*;
@@ -1464,4 +3330,20 @@ mod tests {
     );
     tester.test_all();
   }
+
+  #[cfg(feature = "quickcheck")]
+  #[test]
+  fn arbitrary_test() {
+    use quickcheck::quickcheck;
+    fn prop(i: Interval<i8>) -> bool {
+      i.is_empty() || (i.lb <= i.ub
+        && i.lb >= <i8 as Width>::min_value()
+        && i.ub <= <i8 as Width>::max_value())
+    }
+    fn prop_shrink(i: Interval<i8>) -> bool {
+      i.shrink().all(|s| s.is_empty() || s.lb <= s.ub)
+    }
+    quickcheck(prop as fn(Interval<i8>) -> bool);
+    quickcheck(prop_shrink as fn(Interval<i8>) -> bool);
+  }
 }
@@ -0,0 +1,315 @@
+// Copyright 2015 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Closed and bounded generic intervals mapped to values.
+//!
+//! An interval map associates a value to disjoint intervals, which is useful to attach metadata to the elements of a domain. Intervals are kept ordered as in an [interval set](../interval_set/index.html), but they are not merged since their values can differ. When an inserted interval overlaps existing entries, a merge function decides of the value on the overlapping part.
+//!
+//! ```rust
+//! extern crate interval;
+//!
+//! use interval::Interval;
+//! use interval::interval_map::*;
+//! use interval::ops::*;
+//!
+//! # fn main() {
+//! let mut map = IntervalMap::new();
+//! map.insert(Interval::new(0, 10), 1, |old, new| old + new);
+//! map.insert(Interval::new(5, 15), 2, |old, new| old + new);
+//!
+//! assert_eq!(map.get(&0), Some(&1));
+//! assert_eq!(map.get(&7), Some(&3));
+//! assert_eq!(map.get(&15), Some(&2));
+//! assert_eq!(map.get(&16), None);
+//! assert_eq!(map.query(&Interval::new(8, 20)).count(), 2);
+//! # }
+//! ```
+//!
+//! # See also
+//! [interval set](../interval_set/index.html).
+
+use interval::Interval;
+use gcollections::ops::*;
+use ops::*;
+use std::cmp::Ordering;
+
+use num::Num;
+
+#[derive(Debug, Clone)]
+pub struct IntervalMap<Bound: Width, V> {
+  entries: Vec<(Interval<Bound>, V)>
+}
+
+impl<Bound: Width, V> IntervalMap<Bound, V>
+{
+  pub fn new() -> IntervalMap<Bound, V> {
+    IntervalMap {
+      entries: vec![]
+    }
+  }
+
+  pub fn iter(&self) -> impl Iterator<Item=(&Interval<Bound>, &V)> {
+    self.entries.iter().map(|(key, value)| (key, value))
+  }
+
+  pub fn interval_count(&self) -> usize {
+    self.entries.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.entries.is_empty()
+  }
+}
+
+impl<Bound: Width, V> Default for IntervalMap<Bound, V>
+{
+  fn default() -> IntervalMap<Bound, V> {
+    IntervalMap::new()
+  }
+}
+
+impl<Bound, V> IntervalMap<Bound, V> where
+ Bound: Width + Num
+{
+  pub fn get(&self, value: &Bound) -> Option<&V> {
+    self.entries.binary_search_by(|(key, _)|
+      if &key.upper() < value { Ordering::Less }
+      else if &key.lower() > value { Ordering::Greater }
+      else { Ordering::Equal }
+    ).ok().map(|idx| &self.entries[idx].1)
+  }
+
+  /// Entries whose interval overlaps `interval`, in ascending order.
+  pub fn query(&self, interval: &Interval<Bound>) -> impl Iterator<Item=(&Interval<Bound>, &V)> {
+    let overlapping =
+      if interval.is_empty() { 0..0 }
+      else {
+        let first = self.entries.partition_point(|(key, _)| key.upper() < interval.lower());
+        let last = first + self.entries[first..].partition_point(|(key, _)| key.lower() <= interval.upper());
+        first..last
+      };
+    self.entries[overlapping].iter().map(|(key, value)| (key, value))
+  }
+}
+
+impl<Bound, V> IntervalMap<Bound, V> where
+ Bound: Width + Num,
+ V: Clone
+{
+  /// Maps every element of `interval` to `value`.
+  /// On the parts overlapping existing entries, the value is `merge(old, value)`; the parts of these entries outside of `interval` keep their old value.
+  pub fn insert<F>(&mut self, interval: Interval<Bound>, value: V, merge: F) where
+   F: Fn(&V, &V) -> V
+  {
+    if interval.is_empty() {
+      return;
+    }
+    let one = Bound::one();
+    let old = ::std::mem::take(&mut self.entries);
+    self.entries.reserve(old.len() + 2);
+    // Lower bound of the part of `interval` not yet pushed, `None` when `interval` is fully pushed.
+    let mut remaining = Some(interval.lower());
+    for (key, v) in old {
+      if key.upper() < interval.lower() {
+        self.entries.push((key, v));
+      }
+      else if key.lower() > interval.upper() {
+        if let Some(lb) = remaining.take() {
+          self.entries.push((Interval::new(lb, interval.upper()), value.clone()));
+        }
+        self.entries.push((key, v));
+      }
+      else {
+        if key.lower() < interval.lower() {
+          self.entries.push((Interval::new(key.lower(), interval.lower() - one.clone()), v.clone()));
+        }
+        let overlap = key.intersection(&interval);
+        if let Some(lb) = remaining {
+          if lb < overlap.lower() {
+            self.entries.push((Interval::new(lb, overlap.lower() - one.clone()), value.clone()));
+          }
+        }
+        remaining =
+          if overlap.upper() == interval.upper() { None }
+          else { Some(overlap.upper() + one.clone()) };
+        self.entries.push((overlap, merge(&v, &value)));
+        if key.upper() > interval.upper() {
+          self.entries.push((Interval::new(interval.upper() + one.clone(), key.upper()), v));
+        }
+      }
+    }
+    if let Some(lb) = remaining {
+      self.entries.push((Interval::new(lb, interval.upper()), value));
+    }
+  }
+
+  /// Splits the entry containing `point` into `[lb..point-1]` and `[point..ub]`, both mapped to the value of the entry.
+  /// Nothing is done if `point` is not in an entry or is already its lower bound.
+  pub fn split_at(&mut self, point: Bound) {
+    let idx = self.entries.partition_point(|(key, _)| key.upper() < point);
+    if idx < self.entries.len() && self.entries[idx].0.lower() < point {
+      let (key, value) = self.entries[idx].clone();
+      self.entries[idx].0 = Interval::new(key.lower(), point.clone() - Bound::one());
+      self.entries.insert(idx + 1, (Interval::new(point, key.upper()), value));
+    }
+  }
+}
+
+#[allow(non_upper_case_globals)]
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn make_interval_map(entries: Vec<((i32, i32), char)>) -> IntervalMap<i32, char> {
+    let mut map = IntervalMap::new();
+    for ((lb, ub), value) in entries {
+      map.insert(Interval::new(lb, ub), value, |_, _| panic!("entries must not overlap"));
+    }
+    map
+  }
+
+  fn test_entries(test_id: String, map: &IntervalMap<i32, char>, expected: Vec<((i32, i32), char)>) {
+    let entries: Vec<((i32, i32), char)> = map.iter()
+      .map(|(key, value)| ((key.lower(), key.upper()), *value))
+      .collect();
+    assert_eq!(entries, expected, "{}", test_id);
+  }
+
+  #[test]
+  fn test_insert() {
+    let cases = vec![
+      (1, vec![], vec![]),
+      (2, vec![((1,2),'a')], vec![((1,2),'a')]),
+      (3, vec![((7,9),'b'), ((1,2),'a')], vec![((1,2),'a'), ((7,9),'b')]),
+      (4, vec![((1,2),'a'), ((3,4),'b'), ((-3,-1),'c')], vec![((-3,-1),'c'), ((1,2),'a'), ((3,4),'b')]),
+    ];
+
+    for (id, entries, expected) in cases {
+      let map = make_interval_map(entries);
+      test_entries(format!("test #{} of insert", id), &map, expected);
+    }
+  }
+
+  #[test]
+  fn test_insert_merge() {
+    let merge = |old: &char, new: &char| if old < new { *new } else { *old };
+    // Each case is inserted with the value `'m'` in the map `{[1..2] -> 'a', [7..9] -> 'z'}`.
+    let cases = vec![
+      (1, (3,6), vec![((1,2),'a'), ((3,6),'m'), ((7,9),'z')]),
+      (2, (2,7), vec![((1,1),'a'), ((2,2),'m'), ((3,6),'m'), ((7,7),'z'), ((8,9),'z')]),
+      (3, (0,1), vec![((0,0),'m'), ((1,1),'m'), ((2,2),'a'), ((7,9),'z')]),
+      (4, (8,8), vec![((1,2),'a'), ((7,7),'z'), ((8,8),'z'), ((9,9),'z')]),
+      (5, (-1,11), vec![((-1,0),'m'), ((1,2),'m'), ((3,6),'m'), ((7,9),'z'), ((10,11),'m')]),
+      (6, (10,12), vec![((1,2),'a'), ((7,9),'z'), ((10,12),'m')]),
+    ];
+
+    for (id, (lb, ub), expected) in cases {
+      let mut map = make_interval_map(vec![((1,2),'a'), ((7,9),'z')]);
+      map.insert(Interval::new(lb, ub), 'm', merge);
+      test_entries(format!("test #{} of insert with merge", id), &map, expected);
+    }
+  }
+
+  #[test]
+  fn test_insert_merge_calls() {
+    let mut map = IntervalMap::new();
+    map.insert(Interval::new(0, 10), vec![1], |_, _| panic!("no overlap"));
+    map.insert(Interval::new(5, 15), vec![2], |old, new| {
+      let mut merged = old.clone();
+      merged.extend(new);
+      merged
+    });
+    assert_eq!(map.get(&4), Some(&vec![1]));
+    assert_eq!(map.get(&5), Some(&vec![1, 2]));
+    assert_eq!(map.get(&10), Some(&vec![1, 2]));
+    assert_eq!(map.get(&11), Some(&vec![2]));
+  }
+
+  #[test]
+  fn test_get() {
+    let map = make_interval_map(vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]);
+    let cases = vec![
+      (-1, None), (0, None), (1, Some(&'a')), (2, Some(&'a')), (3, None),
+      (4, Some(&'b')), (5, None), (7, Some(&'c')), (8, Some(&'c')), (9, Some(&'c')), (10, None)
+    ];
+    for (value, expected) in cases {
+      assert_eq!(map.get(&value), expected, "get({}) in {:?}", value, map);
+    }
+    assert_eq!(IntervalMap::<i32, char>::new().get(&0), None);
+  }
+
+  #[test]
+  fn test_query() {
+    let map = make_interval_map(vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]);
+    let cases = vec![
+      // none
+      (1, (-5,0), vec![]),
+      (2, (3,3), vec![]),
+      (3, (5,6), vec![]),
+      (4, (10,20), vec![]),
+      // one
+      (5, (0,1), vec![((1,2),'a')]),
+      (6, (2,3), vec![((1,2),'a')]),
+      (7, (4,4), vec![((4,4),'b')]),
+      (8, (9,12), vec![((7,9),'c')]),
+      // several
+      (9, (2,4), vec![((1,2),'a'), ((4,4),'b')]),
+      (10, (3,8), vec![((4,4),'b'), ((7,9),'c')]),
+      (11, (-1,11), vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+    ];
+    for (id, (lb, ub), expected) in cases {
+      let result: Vec<((i32, i32), char)> = map.query(&Interval::new(lb, ub))
+        .map(|(key, value)| ((key.lower(), key.upper()), *value))
+        .collect();
+      assert_eq!(result, expected, "test #{} of query", id);
+    }
+    assert_eq!(map.query(&Interval::empty()).count(), 0);
+  }
+
+  #[test]
+  fn test_split_at() {
+    // Each case splits the map `{[1..2] -> 'a', [4..4] -> 'b', [7..9] -> 'c'}`.
+    let cases = vec![
+      (1, 0, vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+      (2, 1, vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+      (3, 2, vec![((1,1),'a'), ((2,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+      (4, 3, vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+      (5, 4, vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+      (6, 8, vec![((1,2),'a'), ((4,4),'b'), ((7,7),'c'), ((8,9),'c')]),
+      (7, 9, vec![((1,2),'a'), ((4,4),'b'), ((7,8),'c'), ((9,9),'c')]),
+      (8, 10, vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]),
+    ];
+    for (id, point, expected) in cases {
+      let mut map = make_interval_map(vec![((1,2),'a'), ((4,4),'b'), ((7,9),'c')]);
+      map.split_at(point);
+      test_entries(format!("test #{} of split_at", id), &map, expected);
+    }
+  }
+
+  #[test]
+  fn test_split_at_independent_values() {
+    let mut map = IntervalMap::new();
+    map.insert(Interval::new(0, 10), vec![1], |_, _| panic!("no overlap"));
+    map.split_at(5);
+    assert_eq!(map.interval_count(), 2);
+    assert_eq!(map.get(&4), Some(&vec![1]));
+    assert_eq!(map.get(&5), Some(&vec![1]));
+
+    let keys: Vec<Interval<i32>> = map.iter().map(|(key, _)| *key).collect();
+    assert_eq!(keys, vec![Interval::new(0, 4), Interval::new(5, 10)]);
+    assert_eq!(keys[0].hull(&keys[1]), Interval::new(0, 10));
+
+    map.insert(Interval::new(5, 10), vec![2], |old, new| {
+      let mut merged = old.clone();
+      merged.extend(new);
+      merged
+    });
+    assert_eq!(map.get(&4), Some(&vec![1]));
+    assert_eq!(map.get(&5), Some(&vec![1, 2]));
+  }
+}
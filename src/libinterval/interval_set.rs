@@ -8,7 +8,7 @@
 
 //! Closed and bounded generic interval set.
 //!
-//! It stores intervals in a set. The main advantage is the exact representation of an interval by allowing "holes". For example `[1..2] U [5..6]` is stored as `{[1..2], [5..6]}`. This structure is more space-efficient than a classic set collection (such as `BTreeSet`) if the data stored are mostly contiguous. Of course, it is less light-weight than [interval](../interval/index.html), but we keep the list of intervals as small as possible by merging overlapping intervals.
+//! It stores intervals in a set. The main advantage is the exact representation of an interval by allowing "holes". For example `[1..2] U [5..6]` is stored as `{[1..2], [5..6]}`. This structure is more space-efficient than a classic set collection (such as `BTreeSet`) if the data stored are mostly contiguous. Of course, it is less light-weight than [interval](../interval/index.html), but we keep the list of intervals as small as possible by merging overlapping intervals. This representation is canonical: the components are always maximal, so a component cannot be fragmented without changing the equality and the other operations. To attach data to sub-ranges of a component, see [interval map](../interval_map/index.html) and `IntervalMap::split_at`, or split the set itself with `partition_at`.
 //!
 //! ```rust
 //! extern crate gcollections;
@@ -33,15 +33,35 @@
 
 use interval::Interval;
 use interval::ToInterval;
+use interval::StepIter;
 use trilean::SKleene;
 use gcollections::*;
 use gcollections::ops::*;
 use ops::*;
-use std::iter::{Peekable, IntoIterator};
+use std::iter::{Peekable, IntoIterator, FromIterator};
 use std::fmt::{Formatter, Display, Error};
-use std::ops::{Add, Sub, Mul};
-
-use num::{Zero, Num};
+use std::ops::{Add, Sub, Mul, BitXorAssign};
+use std::marker::PhantomData;
+use std::collections::BTreeMap;
+use std::cmp::{min, max};
+
+use num::{Zero, One, Num, ToPrimitive, FromPrimitive};
+#[cfg(feature = "quickcheck")]
+use quickcheck::{Arbitrary, Gen};
+#[cfg(feature = "proptest")]
+use interval::interval_strategy;
+#[cfg(feature = "proptest")]
+use proptest::strategy::{Strategy, BoxedStrategy};
+#[cfg(feature = "proptest")]
+use proptest::collection::vec as vec_strategy;
+#[cfg(feature = "proptest")]
+use std::fmt::Debug;
+#[cfg(feature = "proptest")]
+use std::ops::RangeInclusive;
+#[cfg(feature = "serde")]
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+#[cfg(feature = "serde")]
+use serde::de::Error as DeError;
 
 #[derive(Debug, Clone)]
 pub struct IntervalSet<Bound: Width> {
@@ -49,6 +69,13 @@ pub struct IntervalSet<Bound: Width> {
   size: Bound::Output
 }
 
+/// Origin of an item in an iteration over two sets, see `IntervalSet::merge_tagged`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tagged<T> {
+  Left(T),
+  Right(T)
+}
+
 impl<Bound: Width> IntervalKind for IntervalSet<Bound> {}
 
 impl<Bound: Width> Collection for IntervalSet<Bound>
@@ -104,6 +131,332 @@ impl<Bound> IntervalSet<Bound> where
     self.intervals.len()
   }
 
+  /// Map from the lower bound to the upper bound of each component.
+  pub fn to_btree_ranges(&self) -> BTreeMap<Bound, Bound> {
+    self.intervals.iter().map(|i| (i.lower(), i.upper())).collect()
+  }
+
+  /// Applies `f` to each component and renormalizes the set afterwards: the components are sorted again, the empty ones are removed and the overlapping or adjacent ones are merged.
+  pub fn modify_components<F>(&mut self, mut f: F) where
+   F: FnMut(&mut Interval<Bound>)
+  {
+    let mut intervals = ::std::mem::take(&mut self.intervals);
+    for i in &mut intervals {
+      f(i);
+    }
+    *self = IntervalSet::from_unsorted(intervals);
+  }
+
+  /// Removes the intervals satisfying `f` and returns them in a new set.
+  pub fn extract_if<F>(&mut self, mut f: F) -> IntervalSet<Bound> where
+   F: FnMut(&Interval<Bound>) -> bool
+  {
+    let intervals = ::std::mem::take(&mut self.intervals);
+    self.size = <<Bound as Width>::Output>::zero();
+    let mut extracted = IntervalSet::empty();
+    for i in intervals {
+      if f(&i) { extracted.push(i); }
+      else { self.push(i); }
+    }
+    extracted
+  }
+
+  /// Difference between `self` and the single interval `iv`. The overlapped components are found by binary search and only them are trimmed or split, the others are copied as is.
+  pub fn remove_interval(&self, iv: &Interval<Bound>) -> IntervalSet<Bound> {
+    if iv.is_empty() { return self.clone(); }
+    let first = self.intervals.partition_point(|i| i.upper() < iv.lower());
+    let last = self.intervals.partition_point(|i| i.lower() <= iv.upper());
+    if first >= last { return self.clone(); }
+    let mut intervals = Vec::with_capacity(self.intervals.len() + 1);
+    intervals.extend_from_slice(&self.intervals[..first]);
+    let left = &self.intervals[first];
+    if left.lower() < iv.lower() {
+      intervals.push(Interval::new(left.lower(), iv.lower() - Bound::one()));
+    }
+    let right = &self.intervals[last - 1];
+    if right.upper() > iv.upper() {
+      intervals.push(Interval::new(iv.upper() + Bound::one(), right.upper()));
+    }
+    let kept = intervals.len();
+    intervals.extend_from_slice(&self.intervals[last..]);
+    let size = self.intervals[first..last].iter().fold(self.size.clone(), |size, i| size - i.size());
+    let size = intervals[first..kept].iter().fold(size, |size, i| size + i.size());
+    IntervalSet { intervals, size }
+  }
+
+  /// In-place union of `self` and `other` returning the number of elements added to `self`, it is zero if `other` is a subset of `self`. The count has the type of `size()` so it does not overflow.
+  pub fn union_reporting(&mut self, other: &IntervalSet<Bound>) -> <Bound as Width>::Output {
+    let before = self.size.clone();
+    *self = self.union(other);
+    self.size.clone() - before
+  }
+
+  /// Splits the set into the elements lower than `pivot` and the elements greater or equal to `pivot`.
+  pub fn partition_at(&self, pivot: Bound) -> (IntervalSet<Bound>, IntervalSet<Bound>) {
+    // Index of the first interval with elements greater or equal to `pivot`.
+    let idx = self.intervals.partition_point(|i| i.upper() < pivot);
+    let mut left = self.intervals[..idx].to_vec();
+    let mut right = self.intervals[idx..].to_vec();
+    if let Some(straddling) = right.first_mut() {
+      if straddling.lower() < pivot {
+        left.push(Interval::new(straddling.lower(), pivot.clone() - Bound::one()));
+        *straddling = Interval::new(pivot, straddling.upper());
+      }
+    }
+    (IntervalSet::from_intervals(left), IntervalSet::from_intervals(right))
+  }
+
+  /// Over-approximates `self` with at most `max_components` components (at least one if `self` is not empty) by filling the smallest gaps between consecutive components first. On equal gaps the leftmost is filled first.
+  pub fn coalesce_to_at_most(&self, max_components: usize) -> IntervalSet<Bound> {
+    let max_components = max(max_components, 1);
+    if self.intervals.len() <= max_components {
+      return self.clone();
+    }
+    // `gaps[k]` is the gap between the components `k` and `k+1`, measured by `width` to avoid overflow.
+    let mut gaps: Vec<(<Bound as Width>::Output, usize)> = self.intervals.windows(2).enumerate()
+      .map(|(k, w)| (Bound::width(&w[0].upper(), &w[1].lower()), k))
+      .collect();
+    gaps.sort();
+    let mut filled = vec![false; gaps.len()];
+    for &(_, k) in gaps.iter().take(self.intervals.len() - max_components) {
+      filled[k] = true;
+    }
+    let mut res = IntervalSet::empty();
+    let mut current = self.intervals[0].clone();
+    for (k, next) in self.intervals[1..].iter().enumerate() {
+      if filled[k] {
+        current = current.hull(next);
+      }
+      else {
+        res.push(current);
+        current = next.clone();
+      }
+    }
+    res.push(current);
+    res
+  }
+
+  /// The first component with at least `length` elements, if any.
+  pub fn first_component_at_least(&self, length: Bound) -> Option<&Interval<Bound>> {
+    if length <= Bound::zero() { return self.intervals.first(); }
+    let shorter = length - Bound::one();
+    self.intervals.iter().find(|i| !size_at_most(i, &shorter))
+  }
+
+  /// The components with at least `min_size` elements, the smaller ones are removed.
+  pub fn drop_small(&self, min_size: Bound) -> IntervalSet<Bound> {
+    if min_size <= Bound::zero() { return self.clone(); }
+    let shorter = min_size - Bound::one();
+    let mut res = IntervalSet::empty();
+    for i in self.intervals.iter().filter(|i| !size_at_most(i, &shorter)) {
+      res.push(i.clone());
+    }
+    res
+  }
+
+  /// Removes and returns the `count` lowest elements of `self` (all of them if `count` exceeds the size), the straddling component is split.
+  pub fn take_prefix(&mut self, count: Bound) -> IntervalSet<Bound> {
+    let mut count = count;
+    let mut taken = vec![];
+    let mut rest = vec![];
+    for i in ::std::mem::take(&mut self.intervals) {
+      if count <= Bound::zero() {
+        rest.push(i);
+      }
+      else if size_at_most(&i, &count) {
+        count = count - (i.upper() - i.lower()) - Bound::one();
+        taken.push(i);
+      }
+      else {
+        let cut = i.lower() + (count - Bound::one());
+        taken.push(Interval::new(i.lower(), cut.clone()));
+        if cut < i.upper() {
+          rest.push(Interval::new(cut + Bound::one(), i.upper()));
+        }
+        count = Bound::zero();
+      }
+    }
+    *self = IntervalSet::from_intervals(rest);
+    IntervalSet::from_intervals(taken)
+  }
+
+  /// Removes and returns the `count` greatest elements of `self` (all of them if `count` exceeds the size), the straddling component is split.
+  pub fn take_suffix(&mut self, count: Bound) -> IntervalSet<Bound> {
+    let mut count = count;
+    let mut taken = vec![];
+    let mut rest = vec![];
+    for i in ::std::mem::take(&mut self.intervals).into_iter().rev() {
+      if count <= Bound::zero() {
+        rest.push(i);
+      }
+      else if size_at_most(&i, &count) {
+        count = count - (i.upper() - i.lower()) - Bound::one();
+        taken.push(i);
+      }
+      else {
+        let cut = i.upper() - (count - Bound::one());
+        taken.push(Interval::new(cut.clone(), i.upper()));
+        if cut > i.lower() {
+          rest.push(Interval::new(i.lower(), cut - Bound::one()));
+        }
+        count = Bound::zero();
+      }
+    }
+    rest.reverse();
+    taken.reverse();
+    *self = IntervalSet::from_intervals(rest);
+    IntervalSet::from_intervals(taken)
+  }
+
+  /// The points where the membership changes in increasing order, with `true` when entering the set (the lower bound of a component) and `false` when leaving it (the value following the upper bound). The leaving point of a component ending at `Width::max_value()` is omitted since it is the end of the domain.
+  pub fn boundaries(&self) -> impl Iterator<Item=(Bound, bool)> + '_ {
+    self.intervals.iter().flat_map(|i| {
+      let leave =
+        if i.upper() == Bound::max_value() { None }
+        else { Some((i.upper() + Bound::one(), false)) };
+      Some((i.lower(), true)).into_iter().chain(leave)
+    })
+  }
+
+  /// Iterates over the components of both sets ordered by their lower bounds, each one tagged with the set it comes from. On equal lower bounds the component of `self` comes first.
+  pub fn merge_tagged<'a>(&'a self, other: &'a IntervalSet<Bound>) -> impl Iterator<Item=Tagged<&'a Interval<Bound>>> + 'a {
+    let mut left = self.intervals.iter().peekable();
+    let mut right = other.intervals.iter().peekable();
+    ::std::iter::from_fn(move || {
+      match (left.peek(), right.peek()) {
+        (Some(l), Some(r)) if r.lower() < l.lower() => right.next().map(Tagged::Right),
+        (Some(_), _) => left.next().map(Tagged::Left),
+        (None, _) => right.next().map(Tagged::Right)
+      }
+    })
+  }
+
+  /// A window of `width` consecutive values containing the most elements of `self`, the leftmost one in case of ties. `None` is returned if `self` is empty.
+  ///
+  /// An optimal window always starts at the lower bound or ends at the upper bound of a component, so only these windows are compared.
+  /// Precondition: `width > 0` and the bounds of these windows do not overflow.
+  pub fn densest_window(&self, width: Bound) -> Option<Interval<Bound>> {
+    debug_assert!(width > Bound::zero(), "The width of a window must be positive.");
+    let zero = <<Bound as Width>::Output>::zero();
+    // `prefix[i]` is the number of elements in the `i` first components.
+    let mut prefix = vec![zero.clone()];
+    for i in &self.intervals {
+      let last = prefix[prefix.len() - 1].clone();
+      prefix.push(last + i.size());
+    }
+    let coverage = |window: &Interval<Bound>| {
+      let first = self.intervals.partition_point(|i| i.upper() < window.lower());
+      let last = self.intervals.partition_point(|i| i.lower() <= window.upper());
+      if first >= last { zero.clone() }
+      else if last - first == 1 { self.intervals[first].intersection(window).size() }
+      else {
+        self.intervals[first].intersection(window).size()
+        + (prefix[last - 1].clone() - prefix[first + 1].clone())
+        + self.intervals[last - 1].intersection(window).size()
+      }
+    };
+    let offset = width - Bound::one();
+    let mut windows: Vec<Interval<Bound>> = self.intervals.iter()
+      .flat_map(|i| vec![
+        Interval::new(i.lower(), i.lower() + offset.clone()),
+        Interval::new(i.upper() - offset.clone(), i.upper())])
+      .collect();
+    windows.sort_by_key(|w| w.lower());
+    let mut best: Option<(Interval<Bound>, <Bound as Width>::Output)> = None;
+    for w in windows {
+      let c = coverage(&w);
+      if best.as_ref().is_none_or(|b| c > b.1) {
+        best = Some((w, c));
+      }
+    }
+    best.map(|b| b.0)
+  }
+
+  /// Groups the consecutive components with the same `key` into sets, in increasing order.
+  pub fn group_by_key<K, F>(&self, key: F) -> Vec<(K, IntervalSet<Bound>)> where
+   K: Eq,
+   F: Fn(&Interval<Bound>) -> K
+  {
+    let mut groups: Vec<(K, IntervalSet<Bound>)> = vec![];
+    for i in &self.intervals {
+      let k = key(i);
+      match groups.last_mut() {
+        Some(&mut (ref last, ref mut group)) if *last == k => group.push(i.clone()),
+        _ => groups.push((k, IntervalSet::from_interval(i.clone())))
+      }
+    }
+    groups
+  }
+
+  /// Union of the intervals `[p-radius..p+radius]` around each point `p`.
+  ///
+  /// Precondition: `radius >= 0` and the bounds of these intervals do not overflow.
+  pub fn cover_points(points: &[Bound], radius: Bound) -> IntervalSet<Bound> {
+    debug_assert!(radius >= Bound::zero(), "The radius of a cover must be positive.");
+    let mut points = points.to_vec();
+    points.sort();
+    let mut res = IntervalSet::empty();
+    for p in points {
+      res.join_or_push(Interval::new(p.clone() - radius.clone(), p + radius.clone()));
+    }
+    res
+  }
+
+  /// `true` if the sets of `parts` are pairwise disjoint and their union is `whole`. All the components are sorted once and swept, instead of comparing every pair of parts.
+  pub fn is_partition_of(parts: &[IntervalSet<Bound>], whole: &IntervalSet<Bound>) -> bool {
+    let mut components: Vec<&Interval<Bound>> = parts.iter()
+      .flat_map(|p| p.intervals.iter())
+      .collect();
+    components.sort_by_key(|i| i.lower());
+    let disjoint = components.windows(2).all(|w| w[0].upper() < w[1].lower());
+    disjoint && {
+      let mut union = IntervalSet::empty();
+      for i in components {
+        union.join_or_push(i.clone());
+      }
+      union == *whole
+    }
+  }
+
+  /// Adds the values of `additions` and removes the values of `removals` in one batch, a value in both lists is removed. Each list is sorted once and merged with `self` instead of inserting values one by one.
+  pub fn apply(&mut self, additions: &[Bound], removals: &[Bound]) {
+    let additions = IntervalSet::cover_points(additions, Bound::zero());
+    let removals = IntervalSet::cover_points(removals, Bound::zero());
+    *self = self.union(&additions).difference(&removals);
+  }
+
+  /// Shifts every element `x` to `(x + shift) mod modulus` over the cyclic domain `[0..modulus-1]`, a component crossing the boundary is split in two.
+  ///
+  /// Precondition: `modulus > 0` and `self` is included in `[0..modulus-1]`.
+  pub fn rotate(&self, shift: Bound, modulus: Bound) -> IntervalSet<Bound> {
+    debug_assert!(modulus > Bound::zero(), "The modulus of a rotation must be positive.");
+    debug_assert!(self.is_empty() ||
+      (self.front().lower() >= Bound::zero() && self.back().upper() < modulus),
+      "The rotated set must be included in [0..modulus-1].");
+    let shift = ((shift % modulus.clone()) + modulus.clone()) % modulus.clone();
+    // Elements greater or equal to `wrap` cross the boundary, the subtraction avoids overflowing `x + shift`.
+    let wrap = modulus.clone() - shift.clone();
+    let mut pieces = vec![];
+    for i in &self.intervals {
+      if i.lower() >= wrap {
+        pieces.push(Interval::new(i.lower() - wrap.clone(), i.upper() - wrap.clone()));
+      }
+      else if i.upper() < wrap {
+        pieces.push(Interval::new(i.lower() + shift.clone(), i.upper() + shift.clone()));
+      }
+      else {
+        pieces.push(Interval::new(i.lower() + shift.clone(), modulus.clone() - Bound::one()));
+        pieces.push(Interval::new(Bound::zero(), i.upper() - wrap.clone()));
+      }
+    }
+    pieces.sort_by_key(|i| i.lower());
+    let mut res = IntervalSet::empty();
+    for i in pieces {
+      res.join_or_push(i);
+    }
+    res
+  }
+
   fn from_interval(i: Interval<Bound>) -> IntervalSet<Bound> {
     let size = i.size().clone();
     IntervalSet {
@@ -112,6 +465,25 @@ impl<Bound> IntervalSet<Bound> where
     }
   }
 
+  // Precondition: `intervals` must be a valid intern representation of the interval set.
+  fn from_intervals(intervals: Vec<Interval<Bound>>) -> IntervalSet<Bound> {
+    let size = intervals.iter().fold(<<Bound as Width>::Output>::zero(),
+      |size, i| size + i.size());
+    IntervalSet {
+      intervals,
+      size
+    }
+  }
+
+  // Normalizes arbitrary intervals: the empty ones are removed, the others are sorted and the overlapping or adjacent ones are merged.
+  fn from_unsorted(mut intervals: Vec<Interval<Bound>>) -> IntervalSet<Bound> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort_by_key(|i| i.lower());
+    let mut res = IntervalSet::empty();
+    res.extend(intervals);
+    res
+  }
+
   fn front<'a>(&'a self) -> &'a Interval<Bound> {
     debug_assert!(!self.is_empty(), "Cannot access the first interval of an empty set.");
     &self.intervals[0]
@@ -245,6 +617,205 @@ impl<Bound> IntervalSet<Bound> where
   }
 }
 
+impl IntervalSet<i64>
+{
+  /// `k` values spaced evenly by rank among the elements of `self`, in increasing order: the `j`-th value is the element of rank `⌊j * size / k⌋`, so the gaps between the components are skipped. If `k` exceeds the size of the set, all its elements are returned.
+  pub fn stratified_sample(&self, k: usize) -> Vec<i64> {
+    let size = self.size() as u128;
+    let k = min(k as u128, size);
+    let mut res = Vec::with_capacity(k as usize);
+    let mut components = self.intervals.iter();
+    let mut current = components.next();
+    // Number of elements in the components before `current`.
+    let mut before = 0u128;
+    for j in 0..k {
+      let rank = j * size / k;
+      while let Some(i) = current {
+        let len = i.size() as u128;
+        if rank < before + len { break; }
+        before += len;
+        current = components.next();
+      }
+      let i = current.expect("the rank is always lower than the size of the set");
+      // Exact in two's complement since the element is in `i`.
+      res.push(i.lower().wrapping_add((rank - before) as u64 as i64));
+    }
+    res
+  }
+
+  /// Average of the elements of `self` rounded to the nearest integer (halves away from zero), each component weighs its size so the result leans toward the denser side of a fragmented set. `None` is returned if `self` is empty.
+  pub fn center_of_mass(&self) -> Option<i64> {
+    if self.is_empty() { return None; }
+    // Twice the sum of the elements, it cannot overflow since `|2 * Σ x| <= 2 * Σ_{x=1}^{2^63} x < 2^127`.
+    let moment: i128 = self.intervals.iter()
+      .map(|i| i.size() as i128 * (i.lower() as i128 + i.upper() as i128))
+      .sum();
+    let size = self.size() as i128;
+    // `moment / (2 * size)` rounded, `+ size` shifts the quotient by one half.
+    let center = (moment.abs() + size) / (2 * size);
+    Some((moment.signum() * center) as i64)
+  }
+}
+
+impl<Bound> IntervalSet<Bound> where
+ Bound: Width + Num,
+ <Bound as Width>::Output: ToPrimitive
+{
+  /// Non-panicking companion of `size()`, `None` is returned if the cardinality cannot be represented in a `u128`.
+  pub fn checked_size(&self) -> Option<u128> {
+    self.intervals.iter().try_fold(0u128, |acc, i|
+      i.checked_size().and_then(|s| acc.checked_add(s)))
+  }
+
+  /// Ratio between the size of `self` and the size of its span `[lower..upper]`, in `(0..1]`. It is `1.0` for a single interval and `0.0` for the empty set.
+  pub fn density(&self) -> f64 {
+    if self.is_empty() { 0.0 }
+    else {
+      self.size().to_f64().unwrap() / self.span().size().to_f64().unwrap()
+    }
+  }
+}
+
+impl<Bound> IntervalSet<Bound> where
+ Bound: Width + Num + Step
+{
+  /// Iterates over the elements of the set in increasing order, across its components. The set itself converts into an iterator over its components.
+  pub fn values(&self) -> Values<'_, Bound> {
+    Values {
+      remaining: self.intervals.iter()
+        .try_fold(0usize, |acc, i| i.step_count().and_then(|n| acc.checked_add(n))),
+      intervals: self.intervals.iter(),
+      current: None
+    }
+  }
+}
+
+/// Iterator over the elements of a set with `Step` bounds, see `IntervalSet::values`.
+#[derive(Debug, Clone)]
+pub struct Values<'a, Bound: 'a>
+{
+  intervals: ::std::slice::Iter<'a, Interval<Bound>>,
+  current: Option<StepIter<Bound>>,
+  // `None` if the number of elements does not fit in a `usize`.
+  remaining: Option<usize>
+}
+
+impl<'a, Bound> Iterator for Values<'a, Bound> where
+ Bound: Width + Num + Step
+{
+  type Item = Bound;
+
+  fn next(&mut self) -> Option<Bound> {
+    loop {
+      if let Some(x) = self.current.as_mut().and_then(Iterator::next) {
+        self.remaining = self.remaining.map(|n| n - 1);
+        return Some(x);
+      }
+      self.current = Some(self.intervals.next()?.iter());
+    }
+  }
+
+  fn size_hint(&self) -> (usize, Option<usize>) {
+    match self.remaining {
+      Some(n) => (n, Some(n)),
+      None => (usize::MAX, None)
+    }
+  }
+}
+
+macro_rules! values_exact_size
+{
+  ( $( $t: ty ),* ) =>
+  {$(
+    impl ExactSizeIterator for Values<'_, $t> {}
+  )*}
+}
+
+// See `StepIter`, the size of the other types might not fit in a `usize`.
+values_exact_size!(u8,u16,i8,i16);
+
+macro_rules! canonical_bytes_impl
+{
+  ( $( $t: ty, $wire: ty ),* ) =>
+  {$(
+    impl IntervalSet<$t>
+    {
+      /// Deterministic encoding of the normalized set, to use as a cache key: the number of components as a big-endian `u64` followed by the bounds of each component, in increasing order and big-endian. Equal sets have the same bytes on every platform (`isize` and `usize` are encoded on 64 bits).
+      pub fn canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.intervals.len() * 2 * ::std::mem::size_of::<$wire>());
+        bytes.extend_from_slice(&(self.intervals.len() as u64).to_be_bytes());
+        for i in &self.intervals {
+          bytes.extend_from_slice(&(i.lower() as $wire).to_be_bytes());
+          bytes.extend_from_slice(&(i.upper() as $wire).to_be_bytes());
+        }
+        bytes
+      }
+    }
+  )*}
+}
+
+canonical_bytes_impl!(u8,u8,u16,u16,u32,u32,u64,u64,u128,u128,usize,u64);
+canonical_bytes_impl!(i8,i8,i16,i16,i32,i32,i64,i64,i128,i128,isize,i64);
+
+impl<Bound> IntervalSet<Bound> where
+ Bound: Width + Num + FromPrimitive,
+ <Bound as Width>::Output: ToPrimitive
+{
+  /// Partitions `self` into `k` sets, ordered and with sizes differing by at most one, components are split where needed. Some parts are empty if `k` exceeds the size of `self`, and no part is returned if `k` is zero.
+  pub fn balanced_split(&self, k: usize) -> Vec<IntervalSet<Bound>> {
+    let mut parts = Vec::with_capacity(k);
+    if k == 0 { return parts; }
+    let total = self.size().to_u128().unwrap();
+    let (base, extra) = (total / k as u128, total % k as u128);
+    let mut components = self.intervals.iter().cloned();
+    let mut current = components.next();
+    for j in 0..k as u128 {
+      let mut quota = base + if j < extra { 1 } else { 0 };
+      let mut part = vec![];
+      while quota > 0 {
+        let i = current.take().expect("the quotas sum up to the size of the set");
+        let len = i.size().to_u128().unwrap();
+        if len <= quota {
+          quota -= len;
+          part.push(i);
+          current = components.next();
+        }
+        else {
+          let cut = advance_bound(i.lower(), quota - 1);
+          part.push(Interval::new(i.lower(), cut.clone()));
+          current = Some(Interval::new(cut + Bound::one(), i.upper()));
+          quota = 0;
+        }
+      }
+      parts.push(IntervalSet::from_intervals(part));
+    }
+    parts
+  }
+}
+
+// `b + distance` where the result is known to be a valid bound, the addition is done in two halves because `distance` might not fit in `Bound` (e.g. across zero for signed bounds).
+fn advance_bound<Bound>(b: Bound, distance: u128) -> Bound where
+ Bound: Width + Num + FromPrimitive
+{
+  let half = distance / 2;
+  let to_bound = |d| Bound::from_u128(d).expect("half of a width fits in the bound type");
+  b + to_bound(half) + to_bound(distance - half)
+}
+
+// `true` if `i.size() <= count`, computed in `Bound` without overflowing.
+fn size_at_most<Bound>(i: &Interval<Bound>, count: &Bound) -> bool where
+ Bound: Width + Num
+{
+  let zero = Bound::zero();
+  if i.lower() >= zero || i.upper() <= zero {
+    i.upper() - i.lower() < *count
+  }
+  else {
+    // `i` contains zero, so `upper - lower` could overflow while `count - upper` and `-lower` cannot.
+    count.clone() - i.upper() > zero.clone() - i.lower()
+  }
+}
+
 fn joinable<Bound>(first: &Interval<Bound>, second: &Interval<Bound>) -> bool where
  Bound: Width + Num
 {
@@ -264,6 +835,31 @@ impl<Bound> Extend<Interval<Bound>> for IntervalSet<Bound> where
   }
 }
 
+/// Collects arbitrary intervals into a normalized set, they can be unsorted, overlapping or empty.
+impl<Bound> FromIterator<Interval<Bound>> for IntervalSet<Bound> where
+ Bound: Width + Num
+{
+  fn from_iter<I>(iterable: I) -> IntervalSet<Bound> where
+   I: IntoIterator<Item=Interval<Bound>>
+  {
+    IntervalSet::from_unsorted(iterable.into_iter().collect())
+  }
+}
+
+/// Collects arbitrary `(lb, ub)` pairs into a normalized set, the reversed pairs (`lb > ub`) are dropped.
+impl<Bound> FromIterator<(Bound, Bound)> for IntervalSet<Bound> where
+ Bound: Width + Num
+{
+  fn from_iter<I>(iterable: I) -> IntervalSet<Bound> where
+   I: IntoIterator<Item=(Bound, Bound)>
+  {
+    iterable.into_iter()
+      .filter(|(lb, ub)| lb <= ub)
+      .map(|i| i.to_interval())
+      .collect()
+  }
+}
+
 impl<Bound: Width + Num> Eq for IntervalSet<Bound> {}
 
 impl<Bound> PartialEq<IntervalSet<Bound>> for IntervalSet<Bound> where
@@ -297,17 +893,38 @@ impl<Bound> Whole for IntervalSet<Bound> where
   }
 }
 
+/// The interval from the lower bound of the first component to the upper bound of the last one, it is empty for the empty set.
+impl<Bound> ConvexHull for IntervalSet<Bound> where
+ Bound: Width + Num
+{
+  type Output = Interval<Bound>;
+
+  fn convex_hull(&self) -> Interval<Bound> {
+    self.span()
+  }
+}
+
 impl<Bound> Bounded for IntervalSet<Bound> where
  Bound: Width + Num + PartialOrd
 {
   fn lower(&self) -> Bound {
-    debug_assert!(!self.is_empty(), "Cannot access lower bound on empty interval.");
-    self.front().lower()
+    self.try_lower().expect("Cannot access lower bound on empty interval.")
   }
 
   fn upper(&self) -> Bound {
-    debug_assert!(!self.is_empty(), "Cannot access upper bound on empty interval.");
-    self.back().upper()
+    self.try_upper().expect("Cannot access upper bound on empty interval.")
+  }
+}
+
+impl<Bound> TryBounded for IntervalSet<Bound> where
+ Bound: Width + Num + PartialOrd
+{
+  fn try_lower(&self) -> Option<Bound> {
+    self.intervals.first().map(|i| i.lower())
+  }
+
+  fn try_upper(&self) -> Option<Bound> {
+    self.intervals.last().map(|i| i.upper())
   }
 }
 
@@ -349,6 +966,31 @@ impl<Bound: Width+Num> Contains for IntervalSet<Bound>
   }
 }
 
+impl<Bound: Width+Num> IntervalSet<Bound>
+{
+  /// `true` if every value of `values` is in `self`, it stops at the first value outside. Each value is looked up by binary search so the values do not need to be sorted.
+  pub fn contains_all_values<I>(&self, values: I) -> bool where
+   I: IntoIterator<Item=Bound>
+  {
+    values.into_iter().all(|v| self.contains(&v))
+  }
+
+  /// `true` if at least one interval of `queries` overlaps `self`. The queries are sorted and merged once, then swept together with `self` in a single pass.
+  pub fn overlaps_any(&self, queries: &[Interval<Bound>]) -> bool {
+    self.overlap(&IntervalSet::from_unsorted(queries.to_vec()))
+  }
+
+  /// `true` if `self \ other` is empty, that is `self ⊆ other`. Unlike `is_subset`, both sets are swept together in a single pass which stops at the first component of `self` not covered by `other`, the difference is never built.
+  pub fn is_covered_by(&self, other: &IntervalSet<Bound>) -> bool {
+    let mut cover = other.intervals.iter().peekable();
+    // Since both sets are normalized, each component of `self` must fit in a single component of `other`.
+    self.intervals.iter().all(|i| {
+      while cover.next_if(|c| c.upper() < i.lower()).is_some() {}
+      cover.peek().is_some_and(|c| i.is_subset(c))
+    })
+  }
+}
+
 fn advance_one<I, F, Item>(a : &mut Peekable<I>, b: &mut Peekable<I>, choose: F) -> Item where
  I: Iterator<Item=Item>,
  F: Fn(&Item, &Item) -> bool,
@@ -529,6 +1171,32 @@ impl<Bound: Width+Num> Difference for IntervalSet<Bound> {
   }
 }
 
+/// Exact difference, the components overlapping `rhs` are trimmed or split (see `remove_interval`).
+impl<Bound: Width+Num> Difference<Interval<Bound>> for IntervalSet<Bound> {
+  type Output = IntervalSet<Bound>;
+
+  fn difference(&self, rhs: &Interval<Bound>) -> IntervalSet<Bound> {
+    self.remove_interval(rhs)
+  }
+}
+
+/// Exact difference of an interval and a set, unlike the difference of two intervals which fills the gap to produce an interval.
+impl<Bound: Width+Num> Difference<IntervalSet<Bound>> for Interval<Bound> {
+  type Output = IntervalSet<Bound>;
+
+  fn difference(&self, rhs: &IntervalSet<Bound>) -> IntervalSet<Bound> {
+    self.clone().to_interval_set().difference(rhs)
+  }
+}
+
+impl<Bound: Width+Num> IntervalSet<Bound>
+{
+  /// Complement of `self` relative to `universe`, that is the elements of `universe` not in `self`.
+  pub fn complement_in(&self, universe: &Interval<Bound>) -> IntervalSet<Bound> {
+    universe.difference(self)
+  }
+}
+
 impl<Bound> SymmetricDifference<Bound> for IntervalSet<Bound> where
   Bound: Width + Num + Clone
 {
@@ -549,6 +1217,81 @@ impl<Bound: Width+Num> SymmetricDifference for IntervalSet<Bound> {
   }
 }
 
+/// In-place symmetric difference, computed in a single sweep over the components of both sets. The result is written in the buffer of `self`, which is only reallocated if it has less than `n + m + 2` slots (`n` and `m` being the numbers of components of both sets).
+impl<'a, Bound: Width+Num> BitXorAssign<&'a IntervalSet<Bound>> for IntervalSet<Bound> {
+  fn bitxor_assign(&mut self, rhs: &'a IntervalSet<Bound>) {
+    let one = Bound::one();
+    let offset = rhs.intervals.len() + 2;
+    // The components of `self` are moved to the back of the buffer and read from there while the result is written from the front. Each step writes at most one component and reads at least one from either set, so with these `m + 2` extra slots the writing never overtakes the reading.
+    let buf = &mut self.intervals;
+    buf.resize(buf.len() + offset, Interval::empty());
+    buf.rotate_right(offset);
+    let mut read = offset;
+    let mut written = 0;
+    let mut b = rhs.intervals.iter().cloned();
+    let mut x = read_next(buf, &mut read);
+    let mut y = b.next();
+    loop {
+      match (x.take(), y.take()) {
+        (None, None) => break,
+        (Some(i), None) => { join_or_write(buf, &mut written, read, i); x = read_next(buf, &mut read); }
+        (None, Some(j)) => { join_or_write(buf, &mut written, read, j); y = b.next(); }
+        (Some(i), Some(j)) => {
+          if i.upper() < j.lower() { join_or_write(buf, &mut written, read, i); x = read_next(buf, &mut read); y = Some(j); }
+          else if j.upper() < i.lower() { join_or_write(buf, &mut written, read, j); y = b.next(); x = Some(i); }
+          else {
+            // The part before the common lower bound belongs to one set only, the common part is removed and the remainder is kept for the next step.
+            if i.lower() < j.lower() {
+              join_or_write(buf, &mut written, read, Interval::new(i.lower(), j.lower() - one.clone()));
+            }
+            else if j.lower() < i.lower() {
+              join_or_write(buf, &mut written, read, Interval::new(j.lower(), i.lower() - one.clone()));
+            }
+            if i.upper() < j.upper() {
+              y = Some(Interval::new(i.upper() + one.clone(), j.upper()));
+              x = read_next(buf, &mut read);
+            }
+            else if j.upper() < i.upper() {
+              x = Some(Interval::new(j.upper() + one.clone(), i.upper()));
+              y = b.next();
+            }
+            else {
+              x = read_next(buf, &mut read);
+              y = b.next();
+            }
+          }
+        }
+      }
+    }
+    buf.truncate(written);
+    self.size = buf.iter().fold(<<Bound as Width>::Output>::zero(), |size, i| size + i.size());
+  }
+}
+
+fn read_next<Bound: Clone>(buf: &[Interval<Bound>], read: &mut usize) -> Option<Interval<Bound>> {
+  let x = buf.get(*read).cloned();
+  if x.is_some() { *read += 1; }
+  x
+}
+
+// Writes `x` after the first `written` components of `buf`, or joins it with the last of them.
+fn join_or_write<Bound: Width+Num>(buf: &mut [Interval<Bound>], written: &mut usize, read: usize, x: Interval<Bound>) {
+  if *written > 0 && joinable(&buf[*written - 1], &x) {
+    buf[*written - 1] = buf[*written - 1].hull(&x);
+  }
+  else {
+    debug_assert!(*written < read, "The result must not overwrite the components not read yet.");
+    buf[*written] = x;
+    *written += 1;
+  }
+}
+
+impl<Bound: Width+Num> BitXorAssign for IntervalSet<Bound> {
+  fn bitxor_assign(&mut self, rhs: IntervalSet<Bound>) {
+    *self ^= &rhs;
+  }
+}
+
 impl<Bound: Width+Num> Overlap for IntervalSet<Bound> {
   fn overlap(&self, rhs: &IntervalSet<Bound>) -> bool {
     let a = &mut self.intervals.iter().cloned().peekable();
@@ -754,13 +1497,19 @@ impl<Bound: Width+Num> ToIntervalSet<Bound> for (Bound, Bound)
   }
 }
 
+impl<Bound: Width+Num> ToIntervalSet<Bound> for Interval<Bound>
+{
+  fn to_interval_set(self) -> IntervalSet<Bound> {
+    if self.is_empty() { IntervalSet::empty() }
+    else { IntervalSet::from_interval(self) }
+  }
+}
+
 impl<Bound> ToIntervalSet<Bound> for Vec<(Bound, Bound)> where
  Bound: Width + Num
 {
   fn to_interval_set(self) -> IntervalSet<Bound> {
-    let mut intervals = IntervalSet::empty();
-    intervals.extend(self.into_iter().map(|i| i.to_interval()));
-    intervals
+    self.into_iter().collect()
   }
 }
 
@@ -781,6 +1530,23 @@ impl<Bound: Display+Width+Num> Display for IntervalSet<Bound> where
   }
 }
 
+impl<Bound: Display+Width+Num> IntervalSet<Bound> where
+ <Bound as Width>::Output: Display
+{
+  /// One-line description of the set for logging, such as `2 intervals, 7 values, span [0..9], largest gap 3`. The largest gap is the greatest number of values missing between two consecutive components.
+  pub fn summary(&self) -> String {
+    let largest_gap = self.intervals.windows(2)
+      .map(|w| Bound::width(&(w[0].upper() + Bound::one()), &(w[1].lower() - Bound::one())))
+      .max()
+      .unwrap_or_else(<<Bound as Width>::Output>::zero);
+    let plural = |singular: bool| if singular { "" } else { "s" };
+    format!("{} interval{}, {} value{}, span {}, largest gap {}",
+      self.intervals.len(), plural(self.intervals.len() == 1),
+      self.size(), plural(self.size() == <<Bound as Width>::Output>::one()),
+      self.span(), largest_gap)
+  }
+}
+
 impl<Bound> Join for IntervalSet<Bound> where
  Bound: Width + Num
 {
@@ -829,21 +1595,318 @@ impl<Bound> Bot for IntervalSet<Bound> where
   }
 }
 
-#[allow(non_upper_case_globals)]
-#[cfg(test)]
-mod tests {
-  use super::*;
+/// Interval set stored as a stream of variable-length deltas rather than absolute bounds.
+///
+/// Each component is encoded as two LEB128 numbers: the distance from the upper bound of the previous component (or from `Width::min_value()` for the first one) to its lower bound, followed by its size minus one. Clustered sets of small intervals therefore take a few bytes per component instead of two full bounds. To avoid decoding from the start, the position of every `CHECKPOINT_STRIDE`-th component is also stored so `contains` is a binary search over these checkpoints followed by a scan of at most `CHECKPOINT_STRIDE` components.
+///
+/// The bounds must be representable as an `i128`, which is the case of every primitive integer type including `i128`, but not of the `u128` values greater than `i128::MAX`.
+///
+/// ```rust
+/// extern crate gcollections;
+/// extern crate interval;
+///
+/// use interval::interval_set::*;
+/// use gcollections::ops::*;
+///
+/// # fn main() {
+/// let set = vec![(1,2), (6,10)].to_interval_set();
+/// let compact = CompactIntervalSet::from(&set);
+/// assert!(compact.contains(&7));
+/// assert!(!compact.contains(&4));
+/// assert_eq!(compact.to_interval_set(), set);
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompactIntervalSet<Bound> {
+  bytes: Vec<u8>,
+  interval_count: usize,
+  checkpoints: Vec<Checkpoint>,
+  phantom: PhantomData<Bound>
+}
 
-  fn test_inside_outside(is: IntervalSet<i32>, inside: Vec<i32>, outside: Vec<i32>) {
-    for i in &inside {
-      assert!(is.contains(i),
-        format!("{} is not contained inside {}, but it should.", i, is));
-    }
-    for i in &outside {
-      assert!(!is.contains(i),
-        format!("{} is contained inside {}, but it should not.", i, is));
-    }
-  }
+/// Number of components between two consecutive checkpoints of a `CompactIntervalSet`.
+const CHECKPOINT_STRIDE: usize = 32;
+
+// Decoding state before a component: its lower bound, the offset of its encoding in `bytes` and the upper bound of the previous component (which its delta is relative to).
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Checkpoint {
+  lower: i128,
+  offset: usize,
+  prev: i128
+}
+
+fn push_varint(bytes: &mut Vec<u8>, mut value: u128) {
+  while value >= 0x80 {
+    bytes.push((value as u8) | 0x80);
+    value >>= 7;
+  }
+  bytes.push(value as u8);
+}
+
+fn read_varint<I: Iterator<Item=u8>>(bytes: &mut I) -> Option<u128> {
+  let mut value = 0u128;
+  let mut shift = 0;
+  for byte in bytes {
+    value |= ((byte & 0x7f) as u128) << shift;
+    if byte & 0x80 == 0 {
+      return Some(value);
+    }
+    shift += 7;
+  }
+  None
+}
+
+fn to_i128<Bound: ToPrimitive>(bound: Bound) -> i128 {
+  bound.to_i128().expect("CompactIntervalSet bounds must be representable as an i128.")
+}
+
+impl<Bound> CompactIntervalSet<Bound> where
+ Bound: Width + Num + ToPrimitive + FromPrimitive
+{
+  /// Number of components (maximal intervals) in the set.
+  pub fn interval_count(&self) -> usize {
+    self.interval_count
+  }
+
+  /// Iterates over the components in increasing order, decoding them on the fly.
+  pub fn iter(&self) -> impl Iterator<Item=Interval<Bound>> + '_ {
+    self.iter_from(0, to_i128(<Bound as Width>::min_value()))
+  }
+
+  fn iter_from(&self, offset: usize, mut prev: i128) -> impl Iterator<Item=Interval<Bound>> + '_ {
+    let mut bytes = self.bytes[offset..].iter().cloned();
+    ::std::iter::from_fn(move || {
+      // Exact in two's complement, see the encoding in `From<&IntervalSet>`.
+      let lb = prev.wrapping_add(read_varint(&mut bytes)? as i128);
+      let ub = lb.wrapping_add(read_varint(&mut bytes)? as i128);
+      prev = ub;
+      Some(Interval::new(
+        Bound::from_i128(lb).unwrap(),
+        Bound::from_i128(ub).unwrap()))
+    })
+  }
+}
+
+impl<Bound> Collection for CompactIntervalSet<Bound>
+{
+  type Item = Bound;
+}
+
+impl<Bound> Contains for CompactIntervalSet<Bound> where
+ Bound: Width + Num + ToPrimitive + FromPrimitive
+{
+  fn contains(&self, value: &Bound) -> bool {
+    let v = to_i128(value.clone());
+    // Last checkpoint starting at or before `value`, only its stride can contain `value`.
+    let idx = self.checkpoints.partition_point(|c| c.lower <= v);
+    if idx == 0 { return false; }
+    let start = &self.checkpoints[idx - 1];
+    self.iter_from(start.offset, start.prev)
+      .take(CHECKPOINT_STRIDE)
+      .take_while(|i| i.lower() <= *value)
+      .any(|i| i.upper() >= *value)
+  }
+}
+
+impl<'a, Bound> From<&'a IntervalSet<Bound>> for CompactIntervalSet<Bound> where
+ Bound: Width + Num + ToPrimitive
+{
+  fn from(set: &'a IntervalSet<Bound>) -> CompactIntervalSet<Bound> {
+    let mut bytes = vec![];
+    let mut checkpoints = vec![];
+    let mut prev = to_i128(<Bound as Width>::min_value());
+    for (k, i) in set.iter().enumerate() {
+      let lb = to_i128(i.lower());
+      let ub = to_i128(i.upper());
+      if k % CHECKPOINT_STRIDE == 0 {
+        checkpoints.push(Checkpoint { lower: lb, offset: bytes.len(), prev });
+      }
+      // The distances between `i128` bounds may exceed `i128::MAX` but always fit in a `u128`, they are exact in two's complement since `prev <= lb <= ub`.
+      push_varint(&mut bytes, (lb as u128).wrapping_sub(prev as u128));
+      push_varint(&mut bytes, (ub as u128).wrapping_sub(lb as u128));
+      prev = ub;
+    }
+    CompactIntervalSet {
+      bytes,
+      interval_count: set.interval_count(),
+      checkpoints,
+      phantom: PhantomData
+    }
+  }
+}
+
+impl<Bound> ToIntervalSet<Bound> for CompactIntervalSet<Bound> where
+ Bound: Width + Num + ToPrimitive + FromPrimitive
+{
+  fn to_interval_set(self) -> IntervalSet<Bound> {
+    let mut res = IntervalSet::empty();
+    for i in self.iter() {
+      res.push(i);
+    }
+    res
+  }
+}
+
+#[cfg(any(feature = "quickcheck", feature = "proptest"))]
+fn union_all<Bound: Width+Num>(intervals: Vec<Interval<Bound>>) -> IntervalSet<Bound> {
+  intervals.into_iter()
+    .filter(|i| !i.is_empty())
+    .fold(IntervalSet::empty(), |res, i| res.union(&IntervalSet::from_interval(i)))
+}
+
+/// Generates normalized interval sets, built from the union of arbitrary intervals.
+#[cfg(feature = "quickcheck")]
+impl<Bound> Arbitrary for IntervalSet<Bound> where
+ Bound: Arbitrary + Width + Num
+{
+  fn arbitrary(g: &mut Gen) -> IntervalSet<Bound> {
+    union_all(Vec::<Interval<Bound>>::arbitrary(g))
+  }
+
+  fn shrink(&self) -> Box<dyn Iterator<Item=IntervalSet<Bound>>> {
+    Box::new(self.intervals.shrink().map(union_all))
+  }
+}
+
+/// Strategy generating normalized interval sets as the union of at most `max_intervals` intervals generated by `interval_strategy(bounds, empty_probability)`. It shrinks toward fewer components, and so toward the empty set, and then toward narrower components. Requires the `proptest` feature.
+#[cfg(feature = "proptest")]
+pub fn interval_set_strategy<Bound>(bounds: RangeInclusive<Bound>, empty_probability: f64, max_intervals: usize) -> BoxedStrategy<IntervalSet<Bound>> where
+ Bound: Width + Num + Debug + 'static,
+ <Bound as Width>::Output: Debug,
+ RangeInclusive<Bound>: Strategy<Value=Bound>
+{
+  vec_strategy(interval_strategy(bounds, empty_probability), 0..=max_intervals)
+    .prop_map(union_all)
+    .boxed()
+}
+
+/// Same distribution as the `quickcheck` generator: the union of at most ten arbitrary intervals.
+#[cfg(feature = "proptest")]
+impl<Bound> ::proptest::arbitrary::Arbitrary for IntervalSet<Bound> where
+ Bound: Width + Num + Debug + 'static,
+ <Bound as Width>::Output: Debug,
+ RangeInclusive<Bound>: Strategy<Value=Bound>
+{
+  type Parameters = ();
+  type Strategy = BoxedStrategy<IntervalSet<Bound>>;
+
+  fn arbitrary_with(_: ()) -> Self::Strategy {
+    interval_set_strategy(<Bound as Width>::min_value()..=<Bound as Width>::max_value(), 0.1, 10)
+  }
+}
+
+/// Serialized as an array of `[lb, ub]` pairs, one for each component. See `run_length` for a more compact representation.
+#[cfg(feature = "serde")]
+impl<Bound> Serialize for IntervalSet<Bound> where
+ Bound: Width + Num + Serialize
+{
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(self.intervals.iter().map(|i| (i.lower(), i.upper())))
+  }
+}
+
+/// The pairs do not need to be sorted or disjoint, they are normalized as by a union, and the pairs with `lb > ub` are ignored. Fails if a bound exceeds the limits of `Width`.
+#[cfg(feature = "serde")]
+impl<'de, Bound> Deserialize<'de> for IntervalSet<Bound> where
+ Bound: Width + Num + Deserialize<'de>
+{
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<IntervalSet<Bound>, D::Error> {
+    let mut pairs = Vec::<(Bound, Bound)>::deserialize(deserializer)?;
+    if pairs.iter().any(|(lb, ub)|
+      *lb < <Bound as Width>::min_value() || *ub > <Bound as Width>::max_value())
+    {
+      return Err(D::Error::custom("interval bound out of the limits of `Width`"));
+    }
+    pairs.retain(|(lb, ub)| lb <= ub);
+    pairs.sort();
+    let mut res = IntervalSet::empty();
+    res.extend(pairs.into_iter().map(|(lb, ub)| Interval::new(lb, ub)));
+    Ok(res)
+  }
+}
+
+/// Compact (de)serialization of interval sets as runs, to use with `#[serde(with = "interval::interval_set::run_length")]`.
+///
+/// A set is represented as `{"base": b, "runs": [[start_delta, len], ...]}` where `b` is the lower bound of the first component, `start_delta` is the distance from the upper bound of the previous component (or from `b` for the first one) to the lower bound of the component and `len` is its size. For example `{[1..2][6..10]}` is `{"base": 1, "runs": [[0, 2], [4, 5]]}`. Deltas and lengths are `u64`, so the bounds must be representable as an `i128`.
+#[cfg(feature = "serde")]
+pub mod run_length
+{
+  use super::*;
+  use serde::{Serialize, Serializer, Deserialize, Deserializer};
+  use serde::de::Error as DeError;
+  use serde::ser::Error as SerError;
+  use std::convert::TryFrom;
+
+  #[derive(Serialize, Deserialize)]
+  struct RunLength<Bound> {
+    base: Bound,
+    runs: Vec<(u64, u64)>
+  }
+
+  pub fn serialize<Bound, S>(set: &IntervalSet<Bound>, serializer: S) -> Result<S::Ok, S::Error> where
+   Bound: Width + Num + ToPrimitive + Serialize,
+   S: Serializer
+  {
+    let base = if set.is_empty() { Bound::zero() } else { set.lower() };
+    let mut prev = base.to_i128().ok_or_else(|| S::Error::custom("bound not representable as an i128"))?;
+    let mut runs = Vec::with_capacity(set.interval_count());
+    for i in set.iter() {
+      let out_of_range = || S::Error::custom("bound not representable as an i128");
+      let lb = i.lower().to_i128().ok_or_else(out_of_range)?;
+      let ub = i.upper().to_i128().ok_or_else(out_of_range)?;
+      let delta = lb.checked_sub(prev).and_then(|d| u64::try_from(d).ok())
+        .ok_or_else(|| S::Error::custom(format!("gap before {} not representable as a u64", lb)))?;
+      let len = ub.checked_sub(lb).and_then(|d| d.checked_add(1)).and_then(|d| u64::try_from(d).ok())
+        .ok_or_else(|| S::Error::custom(format!("length of the run starting at {} not representable as a u64", lb)))?;
+      runs.push((delta, len));
+      prev = ub;
+    }
+    RunLength { base, runs }.serialize(serializer)
+  }
+
+  /// Fails if a run is empty (`len = 0`), if a run overlaps or touches the previous one (`start_delta < 2`), or if a bound exceeds the limits of `Width`.
+  pub fn deserialize<'de, Bound, D>(deserializer: D) -> Result<IntervalSet<Bound>, D::Error> where
+   Bound: Width + Num + ToPrimitive + FromPrimitive + Deserialize<'de>,
+   D: Deserializer<'de>
+  {
+    let encoded = RunLength::<Bound>::deserialize(deserializer)?;
+    let to_bound = |b: i128| Bound::from_i128(b)
+      .filter(|b| *b >= <Bound as Width>::min_value() && *b <= <Bound as Width>::max_value())
+      .ok_or_else(|| D::Error::custom(format!("bound {} out of the limits of `Width`", b)));
+    let mut prev = encoded.base.to_i128()
+      .ok_or_else(|| D::Error::custom("base not representable as an i128"))?;
+    let mut intervals = Vec::with_capacity(encoded.runs.len());
+    for (idx, (delta, len)) in encoded.runs.into_iter().enumerate() {
+      if len == 0 {
+        return Err(D::Error::custom(format!("run {} is empty", idx)));
+      }
+      if idx > 0 && delta < 2 {
+        return Err(D::Error::custom(format!("run {} overlaps or touches the previous run", idx)));
+      }
+      let lb = prev + delta as i128;
+      let ub = lb + (len - 1) as i128;
+      intervals.push(Interval::new(to_bound(lb)?, to_bound(ub)?));
+      prev = ub;
+    }
+    Ok(IntervalSet::from_intervals(intervals))
+  }
+}
+
+#[allow(non_upper_case_globals)]
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn test_inside_outside(is: IntervalSet<i32>, inside: Vec<i32>, outside: Vec<i32>) {
+    for i in &inside {
+      assert!(is.contains(i),
+        format!("{} is not contained inside {}, but it should.", i, is));
+    }
+    for i in &outside {
+      assert!(!is.contains(i),
+        format!("{} is contained inside {}, but it should not.", i, is));
+    }
+  }
 
   // precondition: `intervals` must be a valid intern representation of the interval set.
   fn make_interval_set(intervals: Vec<(i32, i32)>) -> IntervalSet<i32> {
@@ -931,6 +1994,878 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_contains_all_values() {
+    let a = make_interval_set(vec![(1,2),(4,5),(7,9)]);
+    assert!(a.contains_all_values(vec![9, 1, 4, 8, 2, 5, 7]));
+    assert!(!a.contains_all_values(vec![1, 4, 6, 8]));
+    assert!(!a.contains_all_values(vec![10]));
+    assert!(a.contains_all_values(vec![]));
+    assert!(IntervalSet::<i32>::empty().contains_all_values(vec![]));
+    assert!(!IntervalSet::<i32>::empty().contains_all_values(vec![0]));
+
+    // Stops at the first value outside of the set.
+    let mut visited = 0;
+    assert!(!a.contains_all_values([1, 3, 4, 5].iter().map(|&v| { visited += 1; v })));
+    assert_eq!(visited, 2);
+  }
+
+  #[test]
+  fn test_overlaps_any() {
+    let busy = vec![(0,9),(20,29),(40,49),(60,69)].to_interval_set();
+    let one_overlaps = vec![
+      Interval::new(70, 80), Interval::new(10, 19), Interval::new(50, 55),
+      Interval::new(35, 40), Interval::new(30, 39), Interval::empty()];
+    let none_overlap = vec![
+      Interval::new(70, 80), Interval::new(10, 19), Interval::new(50, 59),
+      Interval::new(30, 39), Interval::new(-5, -1), Interval::empty()];
+    assert!(busy.overlaps_any(&one_overlaps));
+    assert!(!busy.overlaps_any(&none_overlap));
+    assert!(!busy.overlaps_any(&[]));
+    assert!(!IntervalSet::<i32>::empty().overlaps_any(&one_overlaps));
+    // Merged queries do not create an overlap.
+    assert!(!busy.overlaps_any(&[Interval::new(10, 14), Interval::new(15, 19)]));
+  }
+
+  #[test]
+  fn test_canonical_bytes() {
+    let direct = vec![(1i32,3),(7,9)].to_interval_set();
+    let unsorted: IntervalSet<i32> = vec![(8,9),(1,2),(7,7),(3,3)].into_iter().collect();
+    let mut built = IntervalSet::singleton(1i32);
+    built.extend(vec![Interval::new(2, 4), Interval::new(7, 9)]);
+    built = built.difference(&4);
+    assert_eq!(unsorted.canonical_bytes(), direct.canonical_bytes());
+    assert_eq!(built.canonical_bytes(), direct.canonical_bytes());
+    assert_eq!(direct.canonical_bytes(),
+      vec![0,0,0,0,0,0,0,2, 0,0,0,1, 0,0,0,3, 0,0,0,7, 0,0,0,9]);
+    assert_ne!(direct.canonical_bytes(), vec![(1i32,3),(7,8)].to_interval_set().canonical_bytes());
+
+    assert_eq!(IntervalSet::<i32>::empty().canonical_bytes(), vec![0; 8]);
+    let negative = IntervalSet::singleton(-2i16);
+    assert_eq!(negative.canonical_bytes(), vec![0,0,0,0,0,0,0,1, 0xff,0xfe, 0xff,0xfe]);
+    assert_eq!(IntervalSet::singleton(5usize).canonical_bytes(), IntervalSet::singleton(5u64).canonical_bytes());
+  }
+
+  #[test]
+  fn test_is_covered_by() {
+    let other = vec![(0,9),(20,29),(40,49)].to_interval_set();
+    let covered = vec![(1,3),(5,9),(40,40),(45,49)].to_interval_set();
+    let partially = vec![(1,3),(25,31),(45,49)].to_interval_set();
+    let cases = vec![
+      (&covered, &other, true),
+      (&partially, &other, false),
+      (&other, &other, true),
+      (&other, &covered, false)
+    ];
+    for (a, b, expected) in cases {
+      assert_eq!(a.is_covered_by(b), expected, "{}.is_covered_by({})", a, b);
+      assert_eq!(a.is_covered_by(b), a.difference(b).is_empty());
+    }
+    let empty = IntervalSet::empty();
+    assert!(empty.is_covered_by(&other));
+    assert!(empty.is_covered_by(&empty));
+    assert!(!other.is_covered_by(&empty));
+    // Stops at the first uncovered component.
+    assert!(!vec![(10,12),(20,21)].to_interval_set().is_covered_by(&other));
+    assert!(!vec![(60,61)].to_interval_set().is_covered_by(&other));
+  }
+
+  #[test]
+  fn test_center_of_mass() {
+    let cases = vec![
+      (vec![(0,10)], Some(5)),
+      (vec![(-10,-6),(6,10)], Some(0)),
+      (vec![(0,1),(8,9)], Some(5)),
+      (vec![(0,0),(90,99)], Some(86)),
+      (vec![(0,29),(100,100)], Some(17)),
+      (vec![(7,7)], Some(7)),
+      (vec![], None)
+    ];
+    for (set, expected) in cases {
+      let set: IntervalSet<i64> = set.to_interval_set();
+      assert_eq!(set.center_of_mass(), expected, "{}.center_of_mass()", set);
+    }
+    let whole: IntervalSet<i64> = IntervalSet::whole();
+    assert_eq!(whole.center_of_mass(), Some(0));
+    let top = vec![(i64::MAX - 1, i64::MAX)].to_interval_set();
+    assert_eq!(top.center_of_mass(), Some(i64::MAX));
+    // Exact beyond the precision of `f64`, and rounded away from zero.
+    let big = 1i64 << 60;
+    let cases = vec![
+      (vec![(big + 1, big + 2)], big + 2),
+      (vec![(big + 1, big + 1), (big + 3, big + 3)], big + 2),
+      (vec![(-2, -1)], -2),
+      (vec![(1, i64::MAX)], 1i64 << 62),
+      (vec![(-i64::MAX, -1), (i64::MAX, i64::MAX)], -(1i64 << 62) + 1)
+    ];
+    for (set, expected) in cases {
+      let set: IntervalSet<i64> = set.to_interval_set();
+      assert_eq!(set.center_of_mass(), Some(expected), "{}.center_of_mass()", set);
+    }
+  }
+
+  #[test]
+  fn test_stratified_sample() {
+    let a = vec![(0,9),(100,109),(1000,1019)].to_interval_set();
+    assert_eq!(a.stratified_sample(4), vec![0, 100, 1000, 1010]);
+    assert_eq!(a.stratified_sample(5), vec![0, 8, 106, 1004, 1012]);
+    assert_eq!(a.stratified_sample(1), vec![0]);
+    assert_eq!(a.stratified_sample(0), vec![]);
+
+    let small = vec![(-3,-2),(5,5)].to_interval_set();
+    assert_eq!(small.stratified_sample(10), vec![-3, -2, 5]);
+    assert_eq!(IntervalSet::<i64>::empty().stratified_sample(3), vec![]);
+
+    let whole = IntervalSet::<i64>::whole();
+    let sample = whole.stratified_sample(4);
+    assert_eq!(sample, vec![i64::MIN + 1, i64::MIN / 2, 0, i64::MAX / 2 + 1]);
+
+    let sparse = vec![(0,0),(1_000_000,1_000_000),(1i64 << 40, (1i64 << 40) + 999)].to_interval_set();
+    let sample = sparse.stratified_sample(100);
+    assert!(sample.windows(2).all(|w| w[0] < w[1]));
+    assert!(sample.iter().all(|v| sparse.contains(v)));
+  }
+
+  #[test]
+  fn test_balanced_split() {
+    let cases = vec![
+      (vec![(0,9)], 3, vec![vec![(0,3)], vec![(4,6)], vec![(7,9)]]),
+      (vec![(0,0),(10,29),(40,40)], 2, vec![vec![(0,0),(10,19)], vec![(20,29),(40,40)]]),
+      (vec![(0,1),(5,5),(9,100)], 4, vec![vec![(0,1),(5,5),(9,29)], vec![(30,53)], vec![(54,77)], vec![(78,100)]]),
+      (vec![(1,1),(3,3)], 3, vec![vec![(1,1)], vec![(3,3)], vec![]]),
+      (vec![], 2, vec![vec![], vec![]]),
+      (vec![(0,9)], 0, vec![])
+    ];
+    for (a, k, expected) in cases {
+      let a = make_interval_set(a);
+      let parts = a.balanced_split(k);
+      let expected: Vec<_> = expected.into_iter().map(make_interval_set).collect();
+      assert_eq!(parts, expected, "balanced_split({}, {})", a, k);
+      if k > 0 {
+        assert!(IntervalSet::is_partition_of(&parts, &a));
+      }
+    }
+
+    let whole = IntervalSet::<i8>::whole();
+    let parts = whole.balanced_split(2);
+    assert_eq!(parts, vec![vec![(-127,0)].to_interval_set(), vec![(1,127)].to_interval_set()]);
+    let whole = IntervalSet::<u64>::whole();
+    let parts = whole.balanced_split(3);
+    assert!(IntervalSet::is_partition_of(&parts, &whole));
+    assert!(parts.iter().all(|p| p.interval_count() == 1));
+  }
+
+  #[test]
+  fn test_values() {
+    let a = make_interval_set(vec![(-3,-1),(1,2),(4,4)]);
+    assert_eq!(a.values().collect::<Vec<_>>(), vec![-3, -2, -1, 1, 2, 4]);
+    assert_eq!(a.values().size_hint(), (6, Some(6)));
+    let mut values = a.values();
+    values.nth(3);
+    assert_eq!(values.size_hint(), (2, Some(2)));
+    assert_eq!(values.collect::<Vec<_>>(), vec![2, 4]);
+    assert_eq!(IntervalSet::<i32>::empty().values().size_hint(), (0, Some(0)));
+    assert_eq!(IntervalSet::<i32>::empty().values().next(), None);
+
+    let top = vec![(0u8,2),(253,254)].to_interval_set();
+    assert_eq!(top.values().collect::<Vec<_>>(), vec![0, 1, 2, 253, 254]);
+    assert_eq!(top.values().len(), top.size() as usize);
+  }
+
+  #[test]
+  fn test_convex_hull() {
+    let cases = vec![
+      (vec![], (1,0)),
+      (vec![(5,5)], (5,5)),
+      (vec![(-3,7)], (-3,7)),
+      (vec![(1,3),(7,9)], (1,9)),
+      (vec![(-5,-5),(0,1),(4,4)], (-5,4))
+    ];
+    for (a, expected) in cases {
+      let a = make_interval_set(a);
+      assert_eq!(a.convex_hull(), expected.to_interval(), "convex_hull({})", a);
+    }
+  }
+
+  #[test]
+  fn test_display() {
+    let cases = vec![
+      (vec![], "{}"),
+      (vec![(5,5)], "[5..5]"),
+      (vec![(-3,7)], "[-3..7]"),
+      (vec![(1,3),(7,9)], "{[1..3][7..9]}"),
+      (vec![(-5,-5),(0,1),(4,4)], "{[-5..-5][0..1][4..4]}")
+    ];
+    for (a, expected) in cases {
+      assert_eq!(format!("{}", make_interval_set(a)), expected);
+    }
+  }
+
+  #[test]
+  fn test_summary() {
+    let cases = vec![
+      (vec![], "0 intervals, 0 values, span {}, largest gap 0"),
+      (vec![(5,5)], "1 interval, 1 value, span [5..5], largest gap 0"),
+      (vec![(0,2),(6,9)], "2 intervals, 7 values, span [0..9], largest gap 3"),
+      (vec![(-10,-5),(0,1),(4,4),(100,199)], "4 intervals, 109 values, span [-10..199], largest gap 95")
+    ];
+    for (a, expected) in cases {
+      assert_eq!(make_interval_set(a).summary(), expected);
+    }
+    let max = <i32 as Width>::max_value();
+    let extremes = vec![(-max,-max),(max,max)].to_interval_set();
+    assert_eq!(extremes.summary(), format!("2 intervals, 2 values, span [{}..{}], largest gap {}", -max, max, u32::MAX - 2));
+  }
+
+  #[cfg(feature = "proptest")]
+  #[test]
+  fn test_proptest_strategy() {
+    use proptest::test_runner::{TestRunner, TestError, TestCaseError};
+    use proptest::arbitrary::any;
+    let normalized = |s: &IntervalSet<i32>| s.intervals.windows(2).all(|w| !joinable(&w[0], &w[1]))
+      && s.intervals.iter().all(|i| !i.is_empty());
+    TestRunner::deterministic().run(&interval_set_strategy(-50..=50, 0.1, 8), |s| {
+      assert!(normalized(&s), "{} is not normalized", s);
+      assert!(s.is_empty() || (s.lower() >= -50 && s.upper() <= 50));
+      assert!(s.interval_count() <= 8);
+      Ok(())
+    }).unwrap();
+    TestRunner::deterministic().run(&any::<IntervalSet<i32>>(), |s| {
+      assert!(normalized(&s), "{} is not normalized", s);
+      Ok(())
+    }).unwrap();
+
+    match TestRunner::deterministic().run(&interval_set_strategy(-1000..=1000, 0.0, 8), |_| Err(TestCaseError::fail("always"))) {
+      Err(TestError::Fail(_, s)) => assert!(s.is_empty()),
+      _ => panic!("the property should fail")
+    }
+    match TestRunner::deterministic().run(&interval_set_strategy(-1000..=1000, 0.0, 8), |s| {
+      if s.interval_count() >= 2 { Err(TestCaseError::fail("fragmented")) } else { Ok(()) }
+    }) {
+      Err(TestError::Fail(_, s)) => assert_eq!(s.interval_count(), 2, "{} is not minimal", s),
+      _ => panic!("the property should fail")
+    }
+  }
+
+  #[test]
+  fn test_from_iter() {
+    let cases = vec![
+      (vec![], vec![]),
+      (vec![(7,9),(1,3),(2,4),(9,12)], vec![(1,4),(7,12)]),
+      (vec![(5,2),(0,0)], vec![(0,0)]),
+      (vec![(5,2)], vec![]),
+      (vec![(10,12),(5,6),(8,9),(7,7)], vec![(5,12)]),
+      (vec![(3,3),(3,3),(-3,-1),(1,0),(-10,-5)], vec![(-10,-5),(-3,-1),(3,3)]),
+      (vec![(0,100),(20,30),(40,50)], vec![(0,100)])
+    ];
+    for (pairs, expected) in cases {
+      let expected = make_interval_set(expected);
+      let from_pairs: IntervalSet<i32> = pairs.iter().cloned().collect();
+      assert_eq!(from_pairs, expected, "from_iter({:?})", pairs);
+      assert_eq!(from_pairs.size(), expected.size());
+      let from_intervals: IntervalSet<i32> = pairs.iter().map(|&(lb, ub)| Interval::new(lb, ub)).collect();
+      assert_eq!(from_intervals, expected, "from_iter({:?})", pairs);
+      assert_eq!(pairs.clone().to_interval_set(), expected);
+    }
+    let messy: IntervalSet<i32> = vec![(7,9),(1,3),(2,4),(9,12)].into_iter().collect();
+    assert_eq!(messy.intersection(&vec![(3,8)].to_interval_set()), vec![(3,4),(7,8)].to_interval_set());
+  }
+
+  #[test]
+  fn test_boundaries() {
+    let cases = vec![
+      (vec![], vec![]),
+      (vec![(1,3),(7,8)], vec![(1,true),(4,false),(7,true),(9,false)]),
+      (vec![(-5,-5),(0,0)], vec![(-5,true),(-4,false),(0,true),(1,false)]),
+      (vec![(10,i32::MAX)], vec![(10,true)])
+    ];
+    for (a, expected) in cases {
+      let a = make_interval_set(a);
+      assert_eq!(a.boundaries().collect::<Vec<_>>(), expected, "boundaries({})", a);
+    }
+    let unsigned = vec![(0u8,3),(200,254)].to_interval_set();
+    assert_eq!(unsigned.boundaries().collect::<Vec<_>>(), vec![(0,true),(4,false),(200,true)]);
+  }
+
+  #[test]
+  fn test_density() {
+    let cases = vec![
+      (vec![], 0.0),
+      (vec![(5,5)], 1.0),
+      (vec![(0,9)], 1.0),
+      (vec![(0,4),(15,19)], 0.5),
+      (vec![(0,0),(999,999)], 0.002),
+      (vec![(-2,-1),(2,3)], 4.0 / 6.0)
+    ];
+    for (a, expected) in cases {
+      let a = make_interval_set(a);
+      assert_eq!(a.density(), expected, "density of {}", a);
+    }
+    assert_eq!(IntervalSet::<i64>::whole().density(), 1.0);
+  }
+
+  #[test]
+  fn test_to_btree_ranges() {
+    let cases = vec![
+      vec![],
+      vec![(1,2)],
+      vec![(-10,-5),(1,2),(7,9),(20,20)]
+    ];
+    for a in cases {
+      let ranges = make_interval_set(a.clone()).to_btree_ranges();
+      assert_eq!(ranges.len(), a.len());
+      assert_eq!(ranges.into_iter().collect::<Vec<_>>(), a);
+    }
+  }
+
+  #[test]
+  fn test_modify_components() {
+    fn test_modify<F>(id: usize, f: F, expected: Vec<(i32,i32)>) where
+     F: FnMut(&mut Interval<i32>)
+    {
+      let mut result = make_interval_set(vec![(0,2),(5,7),(10,12)]);
+      result.modify_components(f);
+      let expected = make_interval_set(expected);
+      test_result(format!("test #{} of modify_components", id), &result, &expected);
+      assert_eq!(result.size(), expected.size(), "test #{} of modify_components", id);
+    }
+
+    test_modify(1, |i| *i = *i + 10, vec![(10,12),(15,17),(20,22)]);
+    test_modify(2, |i| if i.lower() == 0 { *i = *i + 4 }, vec![(4,7),(10,12)]);
+    test_modify(3, |i| if i.lower() == 0 { *i = *i + 3 }, vec![(3,7),(10,12)]);
+    test_modify(4, |i| if i.lower() == 10 { *i = *i - 20 }, vec![(-10,-8),(0,2),(5,7)]);
+    test_modify(5, |i| if i.lower() == 5 { *i = Interval::empty() }, vec![(0,2),(10,12)]);
+    test_modify(6, |i| *i = i.intersection(&(1,11).to_interval()), vec![(1,2),(5,7),(10,11)]);
+    test_modify(7, |i| *i = Interval::new(i.lower() - 2, i.upper() + 2), vec![(-2,14)]);
+  }
+
+  #[test]
+  fn test_try_bounded() {
+    let a = make_interval_set(vec![(-3,-1),(4,5),(11,12)]);
+    assert_eq!(a.try_lower(), Some(-3));
+    assert_eq!(a.try_upper(), Some(12));
+    let empty = IntervalSet::<i32>::empty();
+    assert_eq!(empty.try_lower(), None);
+    assert_eq!(empty.try_upper(), None);
+  }
+
+  #[test]
+  fn test_checked_size() {
+    let empty = IntervalSet::<i32>::empty();
+    let a = vec![(0,5), (10,15)].to_interval_set();
+    let whole_i64 = IntervalSet::<i64>::whole();
+    assert_eq!(empty.checked_size(), Some(0));
+    assert_eq!(a.checked_size(), Some(12));
+    assert_eq!(whole_i64.checked_size(), Some(u64::MAX as u128));
+
+    // The cardinality of a normalized set of `i128` always fits in a `u128`, so we build the overflowing case by hand.
+    let min = <i128 as Width>::min_value();
+    let max = <i128 as Width>::max_value();
+    let near_full = IntervalSet {
+      intervals: vec![Interval::new(min, max - 1), Interval::new(min + 1, max)],
+      size: 0
+    };
+    assert_eq!(near_full.checked_size(), None);
+  }
+
+  #[test]
+  fn test_extract_if() {
+    let a = vec![(-5,-3),(0,1),(3,5),(8,8),(10,15)];
+    // The second and third args are the bounds of the interval to extract.
+    // The last two are the extracted intervals and the remaining ones.
+    let cases = vec![
+      (1, vec![], (0,1), vec![], vec![]),
+      (2, a.clone(), (100,100), vec![], a.clone()),
+      (3, a.clone(), (-5,15), a.clone(), vec![]),
+      (4, a.clone(), (0,8), vec![(0,1),(3,5),(8,8)], vec![(-5,-3),(10,15)]),
+      (5, a.clone(), (3,5), vec![(3,5)], vec![(-5,-3),(0,1),(8,8),(10,15)]),
+      (6, a.clone(), (-5,-3), vec![(-5,-3)], vec![(0,1),(3,5),(8,8),(10,15)]),
+    ];
+
+    for (id, a, (lb, ub), expected, expected_rest) in cases {
+      let mut rest = make_interval_set(a);
+      let extracted = rest.extract_if(|i| i.is_subset(&Interval::new(lb, ub)));
+      test_result(format!("test #{} of extract_if (extracted)", id), &extracted, &make_interval_set(expected.clone()));
+      test_result(format!("test #{} of extract_if (remaining)", id), &rest, &make_interval_set(expected_rest.clone()));
+      assert_eq!(extracted.size(), make_interval_set(expected).size());
+      assert_eq!(rest.size(), make_interval_set(expected_rest).size());
+    }
+  }
+
+  #[test]
+  fn test_remove_interval() {
+    let a = vec![(-5,-3),(0,1),(3,5),(10,15)];
+    // The third arg is the removed interval and the last one the expected result.
+    let cases = vec![
+      (1, vec![], (0,10), vec![]),
+      (2, a.clone(), (1,0), a.clone()),
+      // gap and outside tests
+      (3, a.clone(), (6,9), a.clone()),
+      (4, a.clone(), (-20,-6), a.clone()),
+      (5, a.clone(), (16,20), a.clone()),
+      // trims the edges
+      (6, a.clone(), (-10,-4), vec![(-3,-3),(0,1),(3,5),(10,15)]),
+      (7, a.clone(), (14,30), vec![(-5,-3),(0,1),(3,5),(10,13)]),
+      (8, a.clone(), (5,10), vec![(-5,-3),(0,1),(3,4),(11,15)]),
+      // splits a component
+      (9, a.clone(), (12,13), vec![(-5,-3),(0,1),(3,5),(10,11),(14,15)]),
+      (10, a.clone(), (4,4), vec![(-5,-3),(0,1),(3,3),(5,5),(10,15)]),
+      // spans several components
+      (11, a.clone(), (-4,11), vec![(-5,-5),(12,15)]),
+      (12, a.clone(), (-2,9), vec![(-5,-3),(10,15)]),
+      (13, a.clone(), (-5,15), vec![]),
+    ];
+
+    for (id, a, iv, expected) in cases {
+      let a = make_interval_set(a);
+      let iv = iv.to_interval();
+      let expected = make_interval_set(expected);
+      let result = a.remove_interval(&iv);
+      assert_eq!(result, expected, "test#{} of remove_interval({}, {})", id, a, iv);
+      assert_eq!(result.size(), expected.size(), "test#{} of remove_interval size", id);
+      assert_eq!(result, a.difference(&IntervalSet::from_interval(iv)));
+    }
+  }
+
+  #[test]
+  fn test_union_reporting() {
+    let a = vec![(-5,-3),(0,1),(10,15)];
+    // The last two args are the expected union and number of added elements.
+    let cases = vec![
+      (1, vec![], vec![], vec![], 0),
+      (2, vec![], a.clone(), a.clone(), 11),
+      (3, a.clone(), vec![], a.clone(), 0),
+      (4, a.clone(), vec![(-4,-4),(11,15)], a.clone(), 0),
+      (5, a.clone(), vec![(2,4)], vec![(-5,-3),(0,4),(10,15)], 3),
+      (6, a.clone(), vec![(-10,20)], vec![(-10,20)], 20),
+      (7, a.clone(), vec![(-3,0),(14,16),(30,30)], vec![(-5,1),(10,16),(30,30)], 4),
+    ];
+
+    for (id, a, b, expected, added) in cases {
+      let mut a = make_interval_set(a);
+      let b = make_interval_set(b);
+      let expected = make_interval_set(expected);
+      assert_eq!(a.union_reporting(&b), added, "test#{} of union_reporting", id);
+      assert_eq!(a, expected, "test#{} of union_reporting", id);
+    }
+
+    let mut whole = IntervalSet::<i32>::empty();
+    assert_eq!(whole.union_reporting(&IntervalSet::whole()), u32::MAX);
+  }
+
+  #[test]
+  fn test_interval_difference() {
+    let i0_10 = (0,10).to_interval();
+    // For each case (a, b, expected): `a \ b` where `a` is an interval or a set and `b` an interval.
+    let cases = vec![
+      (1, vec![(0,10)], (3,5), vec![(0,2),(6,10)]),
+      (2, vec![(0,10)], (5,5), vec![(0,4),(6,10)]),
+      (3, vec![(0,10)], (20,30), vec![(0,10)]),
+      (4, vec![(0,10)], (-5,-1), vec![(0,10)]),
+      (5, vec![(0,10)], (-5,20), vec![]),
+      (6, vec![(0,10)], (0,10), vec![]),
+      (7, vec![(0,10)], (-5,0), vec![(1,10)]),
+      (8, vec![(0,10)], (10,12), vec![(0,9)]),
+      (9, vec![(0,10)], (1,0), vec![(0,10)]),
+      (10, vec![(0,2),(5,9)], (2,6), vec![(0,1),(7,9)]),
+      (11, vec![], (2,6), vec![]),
+    ];
+    for (id, a, b, expected) in cases {
+      let a = make_interval_set(a);
+      let b = b.to_interval();
+      let expected = make_interval_set(expected);
+      assert_eq!(a.difference(&b), expected, "test#{} of {} \\ {}", id, a, b);
+      if a.interval_count() == 1 {
+        let ai = a.convex_hull();
+        assert_eq!(ai.difference(&b.to_interval_set()), expected, "test#{} of interval difference", id);
+      }
+    }
+
+    let a = vec![(2,3),(6,6)].to_interval_set();
+    assert_eq!(a.complement_in(&i0_10), vec![(0,1),(4,5),(7,10)].to_interval_set());
+    assert_eq!(a.complement_in(&(3,6).to_interval()), vec![(4,5)].to_interval_set());
+    assert!(a.complement_in(&Interval::empty()).is_empty());
+    assert_eq!(IntervalSet::empty().complement_in(&i0_10), i0_10.to_interval_set());
+    assert!(Interval::<i32>::empty().to_interval_set().is_empty());
+  }
+
+  #[test]
+  fn test_partition_at() {
+    let a = vec![(-5,-3),(0,1),(3,5),(10,15)];
+    // The third arg is the pivot and the two last are the expected lower and upper parts.
+    let cases = vec![
+      (1, vec![], 0, vec![], vec![]),
+      // outside tests
+      (2, a.clone(), -10, vec![], a.clone()),
+      (3, a.clone(), -5, vec![], a.clone()),
+      (4, a.clone(), 16, a.clone(), vec![]),
+      (5, a.clone(), 100, a.clone(), vec![]),
+      // gap tests
+      (6, a.clone(), 2, vec![(-5,-3),(0,1)], vec![(3,5),(10,15)]),
+      (7, a.clone(), 8, vec![(-5,-3),(0,1),(3,5)], vec![(10,15)]),
+      (8, a.clone(), 3, vec![(-5,-3),(0,1)], vec![(3,5),(10,15)]),
+      // inside tests
+      (9, a.clone(), 4, vec![(-5,-3),(0,1),(3,3)], vec![(4,5),(10,15)]),
+      (10, a.clone(), 1, vec![(-5,-3),(0,0)], vec![(1,1),(3,5),(10,15)]),
+      (11, a.clone(), 15, vec![(-5,-3),(0,1),(3,5),(10,14)], vec![(15,15)]),
+      (12, a.clone(), -4, vec![(-5,-5)], vec![(-4,-3),(0,1),(3,5),(10,15)]),
+    ];
+
+    for (id, a, pivot, expected_left, expected_right) in cases {
+      let a = make_interval_set(a);
+      let (left, right) = a.partition_at(pivot);
+      let expected_left = make_interval_set(expected_left);
+      let expected_right = make_interval_set(expected_right);
+      test_result(format!("test #{} of partition_at (left)", id), &left, &expected_left);
+      test_result(format!("test #{} of partition_at (right)", id), &right, &expected_right);
+      assert_eq!(left.size(), expected_left.size(), "test #{} of partition_at", id);
+      assert_eq!(right.size(), expected_right.size(), "test #{} of partition_at", id);
+    }
+  }
+
+  #[test]
+  fn test_compact() {
+    let min = <i32 as Width>::min_value();
+    let max = <i32 as Width>::max_value();
+    let sets = vec![
+      vec![],
+      vec![(1,2)],
+      vec![(1,2),(7,9)],
+      vec![(-5,-3),(0,0),(4,5),(7,9)],
+      vec![(min,max)],
+      vec![(min,min),(max,max)]
+    ];
+    for set in sets {
+      let set = make_interval_set(set);
+      let compact = CompactIntervalSet::from(&set);
+      assert_eq!(compact.interval_count(), set.interval_count());
+      assert!(compact.iter().eq(set.iter().cloned()));
+      for value in vec![min, min+1, -6, -5, -4, -3, -1, 0, 1, 2, 3, 6, 7, 9, 10, max-1, max] {
+        assert_eq!(compact.contains(&value), set.contains(&value),
+          "contains({}) disagrees on {}", value, set);
+      }
+      test_result(format!("round-trip of {}", set), &compact.to_interval_set(), &set);
+    }
+  }
+
+  #[test]
+  fn test_compact_i128() {
+    let min = <i128 as Width>::min_value();
+    let max = <i128 as Width>::max_value();
+    let sets = vec![
+      vec![(min,max)],
+      vec![(min,min),(max,max)],
+      vec![(min,-1),(1,max)],
+      vec![(min,min+1),(0,0),(max-1,max)]
+    ];
+    for set in sets {
+      let set: IntervalSet<i128> = set.to_interval_set();
+      let compact = CompactIntervalSet::from(&set);
+      assert!(compact.iter().eq(set.iter().cloned()));
+      for value in vec![min, min+1, min+2, -1, 0, 1, max-2, max-1, max] {
+        assert_eq!(compact.contains(&value), set.contains(&value),
+          "contains({}) disagrees on {}", value, set);
+      }
+      assert_eq!(compact.to_interval_set(), set);
+    }
+  }
+
+  #[test]
+  fn test_compact_clustered() {
+    let set: IntervalSet<i64> = (0..1000i64)
+      .map(|i| (i * 10, i * 10 + 3))
+      .collect::<Vec<_>>()
+      .to_interval_set();
+    let compact = CompactIntervalSet::from(&set);
+    // The first delta is long (from `i64::MIN + 1`), the others take one byte each.
+    assert!(compact.bytes.len() < 2 * 1000 + 20);
+    assert!(compact.contains(&(999 * 10 + 2)));
+    assert!(!compact.contains(&(999 * 10 + 4)));
+    assert_eq!(compact.checkpoints.len(), 1000 / CHECKPOINT_STRIDE + 1);
+    // Around and across the checkpoints.
+    for value in -5..10010 {
+      assert_eq!(compact.contains(&value), set.contains(&value), "contains({})", value);
+    }
+    assert_eq!(compact.to_interval_set(), set);
+  }
+
+  #[test]
+  fn test_group_by_key() {
+    let parity = |i: &Interval<i32>| i.lower() % 2 == 0;
+    let cases = vec![
+      (1, vec![], vec![]),
+      (2, vec![(0,1),(3,4),(6,7),(9,10)],
+        vec![(true, vec![(0,1)]), (false, vec![(3,4)]), (true, vec![(6,7)]), (false, vec![(9,10)])]),
+      (3, vec![(0,1),(4,5),(7,8),(11,12),(14,15)],
+        vec![(true, vec![(0,1),(4,5)]), (false, vec![(7,8),(11,12)]), (true, vec![(14,15)])]),
+      (4, vec![(0,1),(4,5),(8,8)], vec![(true, vec![(0,1),(4,5),(8,8)])])
+    ];
+
+    for (id, a, expected) in cases {
+      let a = make_interval_set(a);
+      let result = a.group_by_key(parity);
+      assert_eq!(result.len(), expected.len(), "test #{} of group_by_key", id);
+      for ((key, group), (expected_key, expected_group)) in result.into_iter().zip(expected) {
+        let expected_group = make_interval_set(expected_group);
+        assert_eq!(key, expected_key, "test #{} of group_by_key", id);
+        test_result(format!("test #{} of group_by_key", id), &group, &expected_group);
+        assert_eq!(group.size(), expected_group.size(), "test #{} of group_by_key", id);
+      }
+    }
+  }
+
+  #[test]
+  fn test_densest_window() {
+    let cases = vec![
+      (1, vec![(0,0),(10,10),(20,24),(26,29),(50,50)], 10, Some((20,29))),
+      (2, vec![(0,0),(5,9),(11,11)], 3, Some((5,7))),
+      (3, vec![(0,0),(5,9),(11,11)], 8, Some((4,11))),
+      (4, vec![(0,1),(3,3)], 3, Some((-1,1))),
+      (5, vec![(0,100)], 10, Some((0,9))),
+      (6, vec![(-20,-18),(-10,-9)], 1, Some((-20,-20))),
+      (7, vec![], 5, None)
+    ];
+
+    for (id, a, width, expected) in cases {
+      let a = make_interval_set(a);
+      assert_eq!(a.densest_window(width), expected.map(|e| e.to_interval()),
+        "test #{} of densest_window", id);
+    }
+
+    // In a uniformly sparse set, every window contains at most one element.
+    let sparse = make_interval_set(vec![(0,0),(10,10),(20,20),(30,30)]);
+    let window = sparse.densest_window(5).unwrap();
+    assert_eq!(window.size(), 5);
+    assert_eq!(sparse.intersection(&IntervalSet::from_interval(window)).size(), 1);
+  }
+
+  #[test]
+  fn test_merge_tagged() {
+    use super::Tagged::*;
+    let cases = vec![
+      (1, vec![], vec![], vec![]),
+      (2, vec![(1,2)], vec![], vec![Left((1,2))]),
+      (3, vec![], vec![(1,2)], vec![Right((1,2))]),
+      (4, vec![(0,1),(6,7)], vec![(3,4),(9,10)],
+        vec![Left((0,1)), Right((3,4)), Left((6,7)), Right((9,10))]),
+      (5, vec![(0,1),(3,4)], vec![(10,12),(14,15)],
+        vec![Left((0,1)), Left((3,4)), Right((10,12)), Right((14,15))]),
+      (6, vec![(5,6),(20,21)], vec![(0,1),(3,9),(12,13)],
+        vec![Right((0,1)), Right((3,9)), Left((5,6)), Right((12,13)), Left((20,21))]),
+      (7, vec![(0,1)], vec![(0,5)], vec![Left((0,1)), Right((0,5))])
+    ];
+
+    for (id, a, b, expected) in cases {
+      let a = make_interval_set(a);
+      let b = make_interval_set(b);
+      let expected: Vec<Tagged<Interval<i32>>> = expected.into_iter()
+        .map(|t| match t {
+          Left(i) => Left(i.to_interval()),
+          Right(i) => Right(i.to_interval())
+        })
+        .collect();
+      let result: Vec<Tagged<Interval<i32>>> = a.merge_tagged(&b)
+        .map(|t| match t {
+          Left(i) => Left(*i),
+          Right(i) => Right(*i)
+        })
+        .collect();
+      assert_eq!(result, expected, "test #{} of merge_tagged", id);
+    }
+  }
+
+  #[test]
+  fn test_take_prefix_suffix() {
+    let a = vec![(0,4),(10,12),(20,29)];
+    // (count, prefix, rest after prefix, suffix, rest after suffix)
+    let cases = vec![
+      (0, vec![], a.clone(), vec![], a.clone()),
+      (3, vec![(0,2)], vec![(3,4),(10,12),(20,29)], vec![(27,29)], vec![(0,4),(10,12),(20,26)]),
+      (5, vec![(0,4)], vec![(10,12),(20,29)], vec![(25,29)], vec![(0,4),(10,12),(20,24)]),
+      (7, vec![(0,4),(10,11)], vec![(12,12),(20,29)], vec![(23,29)], vec![(0,4),(10,12),(20,22)]),
+      (11, vec![(0,4),(10,12),(20,22)], vec![(23,29)], vec![(12,12),(20,29)], vec![(0,4),(10,11)]),
+      (13, vec![(0,4),(10,12),(20,24)], vec![(25,29)], vec![(10,12),(20,29)], vec![(0,4)]),
+      (18, a.clone(), vec![], a.clone(), vec![]),
+      (100, a.clone(), vec![], a.clone(), vec![])
+    ];
+
+    for (count, prefix, prefix_rest, suffix, suffix_rest) in cases {
+      let mut rest = make_interval_set(a.clone());
+      let taken = rest.take_prefix(count);
+      test_result(format!("take_prefix({})", count), &taken, &make_interval_set(prefix.clone()));
+      test_result(format!("rest of take_prefix({})", count), &rest, &make_interval_set(prefix_rest.clone()));
+      assert_eq!(taken.size(), make_interval_set(prefix).size());
+      assert_eq!(rest.size(), make_interval_set(prefix_rest).size());
+
+      let mut rest = make_interval_set(a.clone());
+      let taken = rest.take_suffix(count);
+      test_result(format!("take_suffix({})", count), &taken, &make_interval_set(suffix.clone()));
+      test_result(format!("rest of take_suffix({})", count), &rest, &make_interval_set(suffix_rest.clone()));
+      assert_eq!(taken.size(), make_interval_set(suffix).size());
+      assert_eq!(rest.size(), make_interval_set(suffix_rest).size());
+    }
+
+    let mut whole = IntervalSet::<i32>::whole();
+    let taken = whole.take_prefix(i32::MAX);
+    assert_eq!(taken, vec![(i32::MIN + 1, -1)].to_interval_set());
+    assert_eq!(whole, vec![(0, i32::MAX)].to_interval_set());
+  }
+
+  #[test]
+  fn test_drop_small() {
+    let a = vec![(-8,-8),(0,4),(7,7),(10,11),(20,29),(40,40)];
+    let cases = vec![
+      (0, a.clone()),
+      (1, a.clone()),
+      (2, vec![(0,4),(10,11),(20,29)]),
+      (3, vec![(0,4),(20,29)]),
+      (6, vec![(20,29)]),
+      (11, vec![])
+    ];
+    for (min_size, expected) in cases {
+      let expected = make_interval_set(expected);
+      assert_eq!(make_interval_set(a.clone()).drop_small(min_size), expected, "drop_small({})", min_size);
+    }
+    assert!(IntervalSet::<i32>::empty().drop_small(2).is_empty());
+    let whole = IntervalSet::<i32>::whole();
+    assert_eq!(whole.drop_small(i32::MAX), whole);
+  }
+
+  #[test]
+  fn test_first_component_at_least() {
+    let a = make_interval_set(vec![(0,4),(10,11),(20,29),(40,49)]);
+    let cases = vec![
+      (0, Some((0,4))),
+      (1, Some((0,4))),
+      (5, Some((0,4))),
+      (6, Some((20,29))),
+      (10, Some((20,29))),
+      (11, None)
+    ];
+    for (length, expected) in cases {
+      assert_eq!(a.first_component_at_least(length), expected.map(|e| e.to_interval()).as_ref(),
+        "first_component_at_least({})", length);
+    }
+    let b = make_interval_set(vec![(0,0),(5,6),(10,12)]);
+    assert_eq!(b.first_component_at_least(2), Some(&(5,6).to_interval()));
+    assert_eq!(IntervalSet::<i32>::empty().first_component_at_least(1), None);
+    let whole = IntervalSet::<i32>::whole();
+    assert_eq!(whole.first_component_at_least(i32::MAX), Some(&Interval::whole()));
+  }
+
+  #[test]
+  fn test_coalesce_to_at_most() {
+    let a = vec![(0,1),(5,6),(8,9),(20,21),(23,30)];
+    let cases = vec![
+      (1, vec![], 2, vec![]),
+      (2, a.clone(), 5, a.clone()),
+      (3, a.clone(), 10, a.clone()),
+      (4, a.clone(), 4, vec![(0,1),(5,9),(20,21),(23,30)]),
+      (5, a.clone(), 3, vec![(0,1),(5,9),(20,30)]),
+      (6, a.clone(), 2, vec![(0,9),(20,30)]),
+      (7, a.clone(), 1, vec![(0,30)]),
+      (8, a.clone(), 0, vec![(0,30)]),
+      (9, vec![(0,0),(2,2),(4,4)], 2, vec![(0,2),(4,4)])
+    ];
+
+    for (id, a, max_components, expected) in cases {
+      let a = make_interval_set(a);
+      let expected = make_interval_set(expected);
+      let result = a.coalesce_to_at_most(max_components);
+      test_result(format!("test #{} of coalesce_to_at_most", id), &result, &expected);
+      assert_eq!(result.size(), expected.size(), "test #{} of coalesce_to_at_most", id);
+      assert!(a.is_subset(&result), "test #{} of coalesce_to_at_most", id);
+    }
+  }
+
+  #[test]
+  fn test_cover_points() {
+    let cases = vec![
+      (1, vec![], 2, vec![]),
+      (2, vec![5], 2, vec![(3,7)]),
+      (3, vec![5], 0, vec![(5,5)]),
+      (4, vec![12, 5, 8], 2, vec![(3,14)]),
+      (5, vec![20, 0, 10], 2, vec![(-2,2),(8,12),(18,22)]),
+      (6, vec![0, 5, 3, 20], 1, vec![(-1,6),(19,21)]),
+      (7, vec![4, 4, 4], 1, vec![(3,5)])
+    ];
+
+    for (id, points, radius, expected) in cases {
+      let expected = make_interval_set(expected);
+      let result = IntervalSet::cover_points(&points, radius);
+      test_result(format!("test #{} of cover_points", id), &result, &expected);
+      assert_eq!(result.size(), expected.size(), "test #{} of cover_points", id);
+    }
+  }
+
+  #[test]
+  fn test_is_partition_of() {
+    let whole = vec![(0,9),(20,29)];
+    let cases = vec![
+      (1, vec![vec![(0,9),(20,29)]], true),
+      (2, vec![vec![(0,4)], vec![(5,9),(20,24)], vec![(25,29)]], true),
+      (3, vec![vec![(20,29)], vec![], vec![(0,3),(7,9)], vec![(4,6)]], true),
+      (4, vec![vec![(0,5)], vec![(5,9),(20,29)]], false),
+      (5, vec![vec![(0,9)], vec![(0,9),(20,29)]], false),
+      (6, vec![vec![(0,4)], vec![(6,9),(20,29)]], false),
+      (7, vec![vec![(0,9)], vec![(20,30)]], false),
+      (8, vec![], false)
+    ];
+
+    let whole = make_interval_set(whole);
+    for (id, parts, expected) in cases {
+      let parts: Vec<IntervalSet<i32>> = parts.into_iter().map(make_interval_set).collect();
+      assert_eq!(IntervalSet::is_partition_of(&parts, &whole), expected,
+        "test #{} of is_partition_of", id);
+    }
+    assert!(IntervalSet::<i32>::is_partition_of(&[], &IntervalSet::empty()));
+  }
+
+  #[test]
+  fn test_apply() {
+    let cases = vec![
+      (1, vec![], vec![], vec![], vec![]),
+      (2, vec![(1,5)], vec![], vec![], vec![(1,5)]),
+      (3, vec![], vec![3, 1, 2, 7], vec![], vec![(1,3),(7,7)]),
+      (4, vec![(1,5)], vec![], vec![3, 1], vec![(2,2),(4,5)]),
+      (5, vec![(1,5),(10,12)], vec![6, 9, 20, 8, 7], vec![2, 11, 20, 0],
+        vec![(1,1),(3,10),(12,12)]),
+      (6, vec![(1,5)], vec![7, 8], vec![8, 7], vec![(1,5)]),
+      (7, vec![(1,5)], vec![6, 6, 6], vec![1, 1], vec![(2,6)])
+    ];
+
+    for (id, a, additions, removals, expected) in cases {
+      let mut a = make_interval_set(a);
+      let expected = make_interval_set(expected);
+      a.apply(&additions, &removals);
+      test_result(format!("test #{} of apply", id), &a, &expected);
+      assert_eq!(a.size(), expected.size(), "test #{} of apply", id);
+    }
+  }
+
+  #[test]
+  fn test_rotate() {
+    let cases = vec![
+      (1, vec![], 3, 24, vec![]),
+      (2, vec![(1,2),(5,8)], 3, 24, vec![(4,5),(8,11)]),
+      (3, vec![(1,2),(20,23)], 2, 24, vec![(0,1),(3,4),(22,23)]),
+      (4, vec![(1,2),(20,22)], 3, 24, vec![(0,1),(4,5),(23,23)]),
+      (5, vec![(1,2),(20,22)], 1, 24, vec![(2,3),(21,23)]),
+      (6, vec![(0,3),(22,23)], 24, 24, vec![(0,3),(22,23)]),
+      (7, vec![(0,3),(22,23)], 2, 24, vec![(0,5)]),
+      (8, vec![(1,2),(5,8)], -3, 24, vec![(2,5),(22,23)]),
+      (9, vec![(0,23)], 7, 24, vec![(0,23)])
+    ];
+
+    for (id, a, shift, modulus, expected) in cases {
+      let a = make_interval_set(a);
+      let expected = make_interval_set(expected);
+      let result = a.rotate(shift, modulus);
+      test_result(format!("test #{} of rotate", id), &result, &expected);
+      assert_eq!(result.size(), expected.size(), "test #{} of rotate", id);
+    }
+  }
+
   #[test]
   fn test_complement() {
     let min = <i32 as Width>::min_value();
@@ -1133,6 +3068,55 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_xor_assign() {
+    let a = make_interval_set(vec![(-3,1),(3,7),(9,11)]);
+    let b = make_interval_set(vec![(1,2),(7,9)]);
+    let mut x = a.clone();
+    x ^= &b;
+    assert_eq!(x, a.symmetric_difference(&b));
+    assert_eq!(x.size(), a.symmetric_difference(&b).size());
+    x ^= &b;
+    test_result(String::from("(a ^ b) ^ b"), &x, &a);
+    assert_eq!(x.size(), a.size());
+    x ^= x.clone();
+    assert!(x.is_empty());
+    assert_eq!(x.size(), 0);
+    x ^= a.clone();
+    test_result(String::from("{} ^ a"), &x, &a);
+  }
+
+  #[test]
+  fn test_xor_assign_cases() {
+    let min = <i32 as Width>::min_value();
+    let max = <i32 as Width>::max_value();
+    let cases = vec![
+      (vec![], vec![(1,2),(7,9)], vec![(1,2),(7,9)]),
+      (vec![(1,2),(7,9)], vec![], vec![(1,2),(7,9)]),
+      (vec![(-3,-1),(4,5),(11,12)], vec![(1,2),(7,9)], vec![(-3,-1),(1,2),(4,5),(7,9),(11,12)]),
+      (vec![(-3,0),(3,6),(10,11)], vec![(1,2),(7,9)], vec![(-3,11)]),
+      (vec![(-1,11)], vec![(1,2),(7,9)], vec![(-1,0),(3,6),(10,11)]),
+      (vec![(1,2)], vec![(-1,11)], vec![(-1,0),(3,11)]),
+      (vec![(min,max)], vec![(min,min),(0,0),(max,max)], vec![(min+1,-1),(1,max-1)]),
+      (vec![(1,2),(7,9)], vec![(1,2),(7,9)], vec![])
+    ];
+    for (a, b, expected) in cases {
+      let (a, b, expected) = (make_interval_set(a), make_interval_set(b), make_interval_set(expected));
+      let mut x = a.clone();
+      x ^= &b;
+      test_result(format!("{} ^= {}", a, b), &x, &expected);
+      assert_eq!(x.size(), expected.size());
+    }
+
+    // The buffer of `self` is reused when it has enough capacity.
+    let mut x = make_interval_set(vec![(0,0),(2,2),(4,4)]);
+    x.intervals.reserve(10);
+    let buffer = x.intervals.as_ptr();
+    x ^= &make_interval_set(vec![(1,1),(3,3),(5,5),(7,7)]);
+    test_result(String::from("single pass"), &x, &make_interval_set(vec![(0,5),(7,7)]));
+    assert_eq!(x.intervals.as_ptr(), buffer);
+  }
+
   #[test]
   fn test_overlap_and_is_disjoint() {
     // Note: the first number is the test id, so it should be easy to identify which test has failed.
@@ -1388,4 +3372,106 @@ mod tests {
       assert!(false, "test 4 of test_iterator: empty interval must not yield an element.");
     }
   }
+
+  #[cfg(feature = "quickcheck")]
+  fn is_normalized(set: &IntervalSet<i32>) -> bool {
+    let size = set.intervals.iter().fold(0, |size, i| size + i.size());
+    set.intervals.iter().all(|i| !i.is_empty())
+      && set.intervals.windows(2).all(|w| !joinable(&w[0], &w[1]))
+      && set.size == size
+  }
+
+  #[cfg(feature = "quickcheck")]
+  #[test]
+  fn test_arbitrary() {
+    use quickcheck::quickcheck;
+    fn prop(set: IntervalSet<i32>) -> bool {
+      is_normalized(&set)
+    }
+    fn prop_shrink(set: IntervalSet<i32>) -> bool {
+      set.shrink().all(|s| is_normalized(&s))
+    }
+    quickcheck(prop as fn(IntervalSet<i32>) -> bool);
+    quickcheck(prop_shrink as fn(IntervalSet<i32>) -> bool);
+  }
+
+  #[cfg(feature = "quickcheck")]
+  mod laws {
+    use super::super::*;
+    use quickcheck::quickcheck;
+
+    type Set = IntervalSet<i32>;
+
+    #[test]
+    fn union_intersection_commutativity() {
+      fn prop(a: Set, b: Set) -> bool {
+        a.union(&b) == b.union(&a) && a.intersection(&b) == b.intersection(&a)
+      }
+      quickcheck(prop as fn(Set, Set) -> bool);
+    }
+
+    #[test]
+    fn union_intersection_associativity() {
+      fn prop(a: Set, b: Set, c: Set) -> bool {
+        a.union(&b).union(&c) == a.union(&b.union(&c))
+          && a.intersection(&b).intersection(&c) == a.intersection(&b.intersection(&c))
+      }
+      quickcheck(prop as fn(Set, Set, Set) -> bool);
+    }
+
+    #[test]
+    fn distributivity() {
+      fn prop(a: Set, b: Set, c: Set) -> bool {
+        a.intersection(&b.union(&c)) == a.intersection(&b).union(&a.intersection(&c))
+          && a.union(&b.intersection(&c)) == a.union(&b).intersection(&a.union(&c))
+      }
+      quickcheck(prop as fn(Set, Set, Set) -> bool);
+    }
+
+    #[test]
+    fn de_morgan() {
+      fn prop(a: Set, b: Set) -> bool {
+        a.union(&b).complement() == a.complement().intersection(&b.complement())
+          && a.intersection(&b).complement() == a.complement().union(&b.complement())
+      }
+      quickcheck(prop as fn(Set, Set) -> bool);
+    }
+
+    #[test]
+    fn idempotence() {
+      fn prop(a: Set) -> bool {
+        a.union(&a) == a && a.intersection(&a) == a && a.complement().complement() == a
+      }
+      quickcheck(prop as fn(Set) -> bool);
+    }
+
+    #[test]
+    fn absorption() {
+      fn prop(a: Set, b: Set) -> bool {
+        a.union(&a.intersection(&b)) == a && a.intersection(&a.union(&b)) == a
+      }
+      quickcheck(prop as fn(Set, Set) -> bool);
+    }
+
+    #[test]
+    fn difference_and_symmetric_difference() {
+      fn prop(a: Set, b: Set) -> bool {
+        a.difference(&b) == a.intersection(&b.complement())
+          && a.symmetric_difference(&b) == a.difference(&b).union(&b.difference(&a))
+      }
+      quickcheck(prop as fn(Set, Set) -> bool);
+    }
+
+    #[test]
+    fn xor_assign() {
+      fn prop(a: Set, b: Set) -> bool {
+        let mut x = a.clone();
+        x ^= &b;
+        let toggled = x == a.symmetric_difference(&b) && x.size() == a.symmetric_difference(&b).size();
+        x ^= &b;
+        toggled && x == a && x.size() == a.size()
+      }
+      quickcheck(prop as fn(Set, Set) -> bool);
+    }
+  }
 }
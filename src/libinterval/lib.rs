@@ -18,7 +18,7 @@
 //!
 //! # Examples
 //!
-//! For examples see the [interval module](interval/index.html), [interval set module](interval_set/index.html).
+//! For examples see the [interval module](interval/index.html), [interval set module](interval_set/index.html) and [interval map module](interval_map/index.html).
 //!
 //! # References
 //! * [Boost Interval Arithmetic Library](http://www.boost.org/doc/libs/1_57_0/libs/numeric/interval/doc/interval.html)
@@ -30,10 +30,20 @@ extern crate num;
 #[macro_use]
 extern crate gcollections;
 extern crate trilean;
+#[cfg(feature = "quickcheck")]
+extern crate quickcheck;
+#[cfg(feature = "proptest")]
+extern crate proptest;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "rand")]
+extern crate rand;
 
 pub mod interval;
 pub mod interval_set;
+pub mod interval_map;
 pub mod ops;
 
 pub use interval::Interval;
 pub use interval_set::IntervalSet;
+pub use interval_map::IntervalMap;
@@ -9,8 +9,10 @@
 //! Interval and bound specific operations.
 
 use gcollections::kind::*;
+use gcollections::Optional;
 use num::{Unsigned, Integer};
 use num::Bounded as NumBounded;
+use std::convert::TryFrom;
 
 pub trait Hull<RHS = Self>
 {
@@ -18,6 +20,20 @@ pub trait Hull<RHS = Self>
   fn hull(&self, rhs: &RHS) -> Self::Output;
 }
 
+/// Smallest convex collection enclosing all the elements of `self`, such as the interval spanning a set. Unlike `Hull`, it is computed from a single collection.
+pub trait ConvexHull
+{
+  type Output;
+  fn convex_hull(&self) -> Self::Output;
+}
+
+/// Size of the space between two collections, such as the gap between two disjoint intervals.
+pub trait Distance<RHS = Self>
+{
+  type Output;
+  fn distance(&self, rhs: &RHS) -> Self::Output;
+}
+
 pub trait Range : Collection
 {
   fn new(lb: Self::Item, ub: Self::Item) -> Self;
@@ -28,6 +44,77 @@ pub trait Whole
   fn whole() -> Self;
 }
 
+/// Non-panicking access to the bounds of a collection, `None` is returned if it is empty.
+pub trait TryBounded : Collection
+{
+  fn try_lower(&self) -> Option<Self::Item>;
+  fn try_upper(&self) -> Option<Self::Item>;
+}
+
+/// The bounds of a singleton are its value.
+impl<T: Clone> TryBounded for Optional<T>
+{
+  fn try_lower(&self) -> Option<T> {
+    self.as_ref().cloned()
+  }
+
+  fn try_upper(&self) -> Option<T> {
+    self.as_ref().cloned()
+  }
+}
+
+/// Intersection borrowing its result from `self` instead of cloning it, so the elements only need `PartialEq`.
+pub trait RefIntersection<RHS = Self> : Collection
+{
+  fn intersection_ref(&self, rhs: &RHS) -> Optional<&Self::Item>;
+}
+
+/// Difference borrowing its result from `self` instead of cloning it, so the elements only need `PartialEq`.
+pub trait RefDifference<RHS = Self> : Collection
+{
+  fn difference_ref(&self, rhs: &RHS) -> Optional<&Self::Item>;
+}
+
+/// Same results as the `Intersection` of `Optional` in `gcollections`, without cloning the value.
+impl<T: PartialEq> RefIntersection for Optional<T>
+{
+  fn intersection_ref(&self, other: &Optional<T>) -> Optional<&T> {
+    Optional::wrap(self.as_ref().filter(|x| other.as_ref() == Some(*x)))
+  }
+}
+
+impl<T: PartialEq> RefIntersection<T> for Optional<T>
+{
+  fn intersection_ref(&self, other: &T) -> Optional<&T> {
+    Optional::wrap(self.as_ref().filter(|x| *x == other))
+  }
+}
+
+/// Same results as the `Difference` of `Optional` in `gcollections`, without cloning the value.
+impl<T: PartialEq> RefDifference for Optional<T>
+{
+  fn difference_ref(&self, other: &Optional<T>) -> Optional<&T> {
+    Optional::wrap(self.as_ref().filter(|x| other.as_ref() != Some(*x)))
+  }
+}
+
+impl<T: PartialEq> RefDifference<T> for Optional<T>
+{
+  fn difference_ref(&self, other: &T) -> Optional<&T> {
+    Optional::wrap(self.as_ref().filter(|x| *x != other))
+  }
+}
+
+/// Discrete bound with a successor function, similar to the unstable `std::iter::Step`. It is enough for iterating, counting and checking the adjacency of intervals over domains that are not numbers, such as ordinal enumerations.
+pub trait Step : Ord + Clone
+{
+  /// The next value, or `None` if `self` is the greatest value of the domain.
+  fn successor(&self) -> Option<Self>;
+
+  /// The number of successor steps from `start` to `end`, or `None` if `start > end` or if it does not fit in a `usize`.
+  fn steps_between(start: &Self, end: &Self) -> Option<usize>;
+}
+
 /// Limit of a bound for which the distance between `min_value()` and `max_value()` can be represented in the type `Output`.
 pub trait Width : Ord + Clone
 {
@@ -55,6 +142,7 @@ macro_rules! unsigned_width_impl
         <$t as NumBounded>::min_value()
       }
 
+      #[inline]
       fn width(lower: &$t, upper: &$t) -> $t {
         let lower = *lower;
         let upper = *upper;
@@ -83,6 +171,7 @@ macro_rules! signed_width_impl
         <$t as NumBounded>::min_value() + 1
       }
 
+      #[inline]
       fn width(lower: &$t, upper: &$t) -> $u {
         let lower = *lower;
         let upper = *upper;
@@ -102,8 +191,32 @@ macro_rules! signed_width_impl
   )*}
 }
 
-unsigned_width_impl!(u8,u16,u32,u64,usize);
-signed_width_impl!(i8,u8,i16,u16,i32,u32,i64,u64,isize,usize);
+unsigned_width_impl!(u8,u16,u32,u64,u128,usize);
+signed_width_impl!(i8,u8,i16,u16,i32,u32,i64,u64,i128,u128,isize,usize);
+
+macro_rules! step_impl
+{
+  ( $( $t: ty, $u: ty ),* ) =>
+  {$(
+    impl Step for $t
+    {
+      fn successor(&self) -> Option<$t> {
+        self.checked_add(1)
+      }
+
+      fn steps_between(start: &$t, end: &$t) -> Option<usize> {
+        if start > end { None }
+        else {
+          // Exact in two's complement since `start <= end`.
+          usize::try_from(end.wrapping_sub(*start) as $u).ok()
+        }
+      }
+    }
+  )*}
+}
+
+step_impl!(u8,u8,u16,u16,u32,u32,u64,u64,u128,u128,usize,usize);
+step_impl!(i8,u8,i16,u16,i32,u32,i64,u64,i128,u128,isize,usize);
 
 #[allow(non_upper_case_globals)]
 #[cfg(test)]
@@ -112,6 +225,48 @@ mod tests {
   use gcollections::ops::*;
   use interval::*;
 
+  #[test]
+  fn try_bounded_optional() {
+    let empty: Optional<i32> = Optional::empty();
+    let five = Optional::singleton(5);
+    assert_eq!(empty.try_lower(), None);
+    assert_eq!(empty.try_upper(), None);
+    assert_eq!(five.try_lower(), Some(5));
+    assert_eq!(five.try_upper(), Some(5));
+  }
+
+  #[test]
+  fn ref_intersection_difference_optional() {
+    // Not `Clone`, so the owned operations of `Optional` are not available.
+    #[derive(Debug, PartialEq)]
+    struct Domain(Vec<i32>);
+    let a = Optional::singleton(Domain(vec![1, 2]));
+    let a2 = Optional::singleton(Domain(vec![1, 2]));
+    let b = Optional::singleton(Domain(vec![3]));
+    let empty: Optional<Domain> = Optional::empty();
+
+    assert_eq!(*a.intersection_ref(&a2), Some(&Domain(vec![1, 2])));
+    assert!(a.intersection_ref(&b).is_empty());
+    assert!(a.intersection_ref(&empty).is_empty());
+    assert!(empty.intersection_ref(&a).is_empty());
+    assert_eq!(*a.intersection_ref(&Domain(vec![1, 2])), Some(&Domain(vec![1, 2])));
+    assert!(b.intersection_ref(&Domain(vec![1, 2])).is_empty());
+
+    assert!(a.difference_ref(&a2).is_empty());
+    assert_eq!(*a.difference_ref(&b), Some(&Domain(vec![1, 2])));
+    assert_eq!(*a.difference_ref(&empty), Some(&Domain(vec![1, 2])));
+    assert!(empty.difference_ref(&a).is_empty());
+    assert!(a.difference_ref(&Domain(vec![1, 2])).is_empty());
+    assert_eq!(*b.difference_ref(&Domain(vec![1, 2])), Some(&Domain(vec![3])));
+
+    // Agrees with the owned operations.
+    let (x, y, z) = (Optional::singleton(1), Optional::singleton(1), Optional::singleton(2));
+    for (l, r) in [(x, y), (x, z), (x, Optional::empty()), (Optional::empty(), z)] {
+      assert_eq!(l.intersection_ref(&r).cloned(), *l.intersection(&r));
+      assert_eq!(l.difference_ref(&r).cloned(), *l.difference(&r));
+    }
+  }
+
   #[test]
   fn strict_shrink_left() {
     let empty: Interval<u32> = Interval::empty();
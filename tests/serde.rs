@@ -0,0 +1,118 @@
+// Copyright 2015 Pierre Talbot (IRCAM)
+
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! JSON round-trips of the `serde` feature, run with `cargo test --features serde`. They live outside of the library so the `PartialEq` impls of `serde_json` do not leak into its unit tests.
+
+#![cfg(feature = "serde")]
+
+extern crate gcollections;
+extern crate interval;
+extern crate serde_json;
+
+use interval::Interval;
+use interval::interval_set::*;
+use interval::ops::*;
+use gcollections::ops::*;
+
+#[test]
+fn interval_json() {
+  let empty = Interval::new_discrete(1, 0);
+  let invalid = Interval::new_discrete(10, -10);
+  let cases = vec![
+    (Interval::new(0, 10), r#"{"lb":0,"ub":10}"#),
+    (Interval::new(-5, -1), r#"{"lb":-5,"ub":-1}"#),
+    (Interval::new(0, 0), r#"{"lb":0,"ub":0}"#),
+    (empty, "null"),
+    (invalid, "null")
+  ];
+  for (x, json) in cases {
+    assert_eq!(serde_json::to_string(&x).unwrap(), json);
+    assert_eq!(serde_json::from_str::<Interval<i32>>(json).unwrap(), x);
+  }
+  assert_eq!(serde_json::from_str::<Interval<i32>>(r#"{"lb":10,"ub":0}"#).unwrap(), empty);
+  assert!(serde_json::from_str::<Interval<i32>>(r#"{"lb":-2147483648,"ub":0}"#).is_err());
+  assert!(serde_json::from_str::<Interval<u8>>(r#"{"lb":0,"ub":255}"#).is_err());
+  assert!(serde_json::from_str::<Interval<i32>>(r#"{"lb":0}"#).is_err());
+}
+
+fn to_json(set: &IntervalSet<i32>) -> String {
+  let mut buf = vec![];
+  run_length::serialize(set, &mut serde_json::Serializer::new(&mut buf)).unwrap();
+  String::from_utf8(buf).unwrap()
+}
+
+fn from_json(json: &str) -> Result<IntervalSet<i32>, serde_json::Error> {
+  run_length::deserialize(&mut serde_json::Deserializer::from_str(json))
+}
+
+#[test]
+fn interval_set_pairs() {
+  let cases = vec![
+    (vec![], "[]"),
+    (vec![(1,2),(6,10)], "[[1,2],[6,10]]"),
+    (vec![(-10,-8),(0,3)], "[[-10,-8],[0,3]]")
+  ];
+  for (set, json) in cases {
+    let set = set.to_interval_set();
+    assert_eq!(serde_json::to_string(&set).unwrap(), json);
+    let decoded: IntervalSet<i32> = serde_json::from_str(json).unwrap();
+    assert_eq!(decoded, set);
+    assert_eq!(decoded.size(), set.size());
+  }
+
+  let normalized: IntervalSet<i32> = serde_json::from_str("[[6,10],[1,2],[8,12],[3,3],[20,15],[2,4]]").unwrap();
+  assert_eq!(normalized, vec![(1,4),(6,12)].to_interval_set());
+  assert_eq!(normalized.size(), 11);
+  assert!(serde_json::from_str::<IntervalSet<i32>>("[[-2147483648,0]]").is_err());
+  assert!(serde_json::from_str::<IntervalSet<i32>>("[[0]]").is_err());
+}
+
+#[test]
+fn run_length_round_trip() {
+  let cases = vec![
+    (vec![], r#"{"base":0,"runs":[]}"#),
+    (vec![(1,2),(6,10)], r#"{"base":1,"runs":[[0,2],[4,5]]}"#),
+    (vec![(-10,-8),(-5,-5),(0,3),(100,199)], r#"{"base":-10,"runs":[[0,3],[3,1],[5,4],[97,100]]}"#),
+    (vec![(i32::MIN + 1, i32::MAX)], r#"{"base":-2147483647,"runs":[[0,4294967295]]}"#)
+  ];
+  for (set, json) in cases {
+    let set = set.to_interval_set();
+    assert_eq!(to_json(&set), json);
+    let decoded = from_json(json).unwrap();
+    assert_eq!(decoded, set);
+    assert_eq!(decoded.size(), set.size());
+  }
+}
+
+#[test]
+fn run_length_invalid_runs() {
+  assert!(from_json(r#"{"base":1,"runs":[[0,2],[4,0]]}"#).is_err());
+  assert!(from_json(r#"{"base":1,"runs":[[0,2],[1,5]]}"#).is_err());
+  assert!(from_json(r#"{"base":1,"runs":[[0,2],[0,5]]}"#).is_err());
+  assert!(from_json(r#"{"base":2147483647,"runs":[[0,2]]}"#).is_err());
+  assert!(from_json(r#"{"base":1,"runs":[[0,2],[2,5]]}"#).is_ok());
+}
+
+#[test]
+fn run_length_round_trip_i128() {
+  let encode = |set: &IntervalSet<i128>| -> Result<Vec<u8>, serde_json::Error> {
+    let mut buf = vec![];
+    run_length::serialize(set, &mut serde_json::Serializer::new(&mut buf)).map(|_| buf)
+  };
+  let decode = |json: &[u8]| -> IntervalSet<i128> {
+    run_length::deserialize(&mut serde_json::Deserializer::from_slice(json)).unwrap()
+  };
+  let narrow = vec![(-5i128, -1), (1i128 << 70, (1i128 << 70) + 3)].to_interval_set();
+  assert!(encode(&narrow).is_err(), "the gap exceeds u64::MAX");
+  let long = vec![(0i128, 1i128 << 64)].to_interval_set();
+  assert!(encode(&long).is_err(), "the length exceeds u64::MAX");
+  assert!(encode(&IntervalSet::<i128>::whole()).is_err());
+
+  let within = vec![(-5i128, -1), (u64::MAX as i128 - 4, u64::MAX as i128 - 2)].to_interval_set();
+  assert_eq!(decode(&encode(&within).unwrap()), within);
+}